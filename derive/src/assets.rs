@@ -0,0 +1,139 @@
+use proc_macro2::{Delimiter, Span, TokenStream, TokenTree};
+use std::path::{Path, PathBuf};
+use syn::LitStr;
+
+/// Kind of a literal path found in an asset effect.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PathKind {
+    File,
+    Folder,
+}
+
+/// A string literal path in `@load`, `@load_with`, `@load_all` or `@load_folder`.
+pub(crate) struct LoadPath {
+    pub(crate) path: LitStr,
+    pub(crate) kind: PathKind,
+}
+
+fn push_literal(tt: Option<&TokenTree>, kind: PathKind, result: &mut Vec<LoadPath>) {
+    let Some(TokenTree::Literal(lit)) = tt else {
+        return;
+    };
+    if let Ok(path) = syn::parse_str::<LitStr>(&lit.to_string()) {
+        let path = LitStr::new(&path.value(), lit.span());
+        result.push(LoadPath { path, kind });
+    }
+}
+
+/// Collect literal paths used in asset effects, including inside macro invocations.
+pub(crate) fn load_paths(tokens: TokenStream) -> Vec<LoadPath> {
+    let mut result = Vec::new();
+    collect(tokens, &mut result);
+    result
+}
+
+fn collect(tokens: TokenStream, result: &mut Vec<LoadPath>) {
+    let tokens: Vec<_> = tokens.into_iter().collect();
+    for (index, tt) in tokens.iter().enumerate() {
+        match tt {
+            TokenTree::Group(group) => collect(group.stream(), result),
+            TokenTree::Punct(p) if p.as_char() == '@' => {
+                let Some(TokenTree::Ident(effect)) = tokens.get(index + 1) else {
+                    continue;
+                };
+                let next = tokens.get(index + 2);
+                match effect.to_string().as_str() {
+                    "load" => push_literal(next, PathKind::File, result),
+                    "load_folder" => push_literal(next, PathKind::Folder, result),
+                    "load_with" => match next {
+                        Some(TokenTree::Group(g)) => push_literal(
+                            g.stream().into_iter().next().as_ref(),
+                            PathKind::File,
+                            result,
+                        ),
+                        _ => push_literal(next, PathKind::File, result),
+                    },
+                    "load_all" => {
+                        if let Some(TokenTree::Group(g)) = next {
+                            if g.delimiter() == Delimiter::Bracket {
+                                for tt in g.stream() {
+                                    push_literal(Some(&tt), PathKind::File, result)
+                                }
+                            }
+                        }
+                    }
+                    _ => (),
+                }
+            }
+            _ => (),
+        }
+    }
+}
+
+/// Root of the asset folder, `dir` or `assets` relative to `BEVY_ASSET_ROOT` or `CARGO_MANIFEST_DIR`.
+pub(crate) fn asset_root(dir: Option<&str>) -> Option<PathBuf> {
+    let base = std::env::var("BEVY_ASSET_ROOT")
+        .or_else(|_| std::env::var("CARGO_MANIFEST_DIR"))
+        .ok()?;
+    Some(PathBuf::from(base).join(dir.unwrap_or("assets")))
+}
+
+/// Track a file or directory for recompilation, requires feature `nightly`.
+///
+/// A tracked directory recompiles when its entries are added or removed.
+#[cfg(feature = "nightly")]
+fn track(path: &Path) {
+    if let Some(path) = path.to_str() {
+        proc_macro::tracked::path(path);
+    }
+}
+
+#[cfg(not(feature = "nightly"))]
+fn track(_: &Path) {}
+
+/// Emit compile errors for literal paths that do not exist in the asset folder `dir`,
+/// checked paths and their directories are tracked for recompilation.
+pub(crate) fn check_assets(tokens: TokenStream, dir: Option<&LitStr>) -> TokenStream {
+    let Some(root) = asset_root(dir.map(LitStr::value).as_deref()) else {
+        let e = syn::Error::new(Span::call_site(), "Cannot find the asset folder.");
+        return e.into_compile_error();
+    };
+    let mut errors = TokenStream::new();
+    let mut tracked = Vec::new();
+    for LoadPath { path, kind } in load_paths(tokens) {
+        let value = path.value();
+        // Skip asset sources like `embedded://`.
+        if value.contains("://") {
+            continue;
+        }
+        // Remove the label.
+        let file = value.split('#').next().unwrap_or_default();
+        let full_path = root.join(file);
+        let exists = match kind {
+            PathKind::File => full_path.is_file(),
+            PathKind::Folder => full_path.is_dir(),
+        };
+        // Track the path and its closest existing directory, so creating,
+        // modifying or deleting it triggers a recompilation.
+        let dir = full_path.ancestors().skip(1).find(|x| x.is_dir());
+        for path in [exists.then_some(full_path.as_path()), dir]
+            .into_iter()
+            .flatten()
+        {
+            if !tracked.contains(&path.to_path_buf()) {
+                track(path);
+                tracked.push(path.to_path_buf());
+            }
+        }
+        if !exists {
+            errors.extend(
+                syn::Error::new(
+                    path.span(),
+                    format!("Asset `{value}` not found in `{}`.", root.display()),
+                )
+                .into_compile_error(),
+            );
+        }
+    }
+    errors
+}
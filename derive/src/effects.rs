@@ -0,0 +1,90 @@
+use proc_macro2::{Delimiter, Group, TokenStream, TokenTree};
+
+fn is_punct(tt: &TokenTree, c: char) -> bool {
+    matches!(tt, TokenTree::Punct(p) if p.as_char() == c)
+}
+
+/// Rewrite `@load_with "path", |s| ..` as `@load_with ("path", |s| ..)`,
+/// since `meta_default_constructor` splits fields on commas.
+pub(crate) fn rewrite_effects(tokens: TokenStream) -> TokenStream {
+    let tokens: Vec<_> = tokens.into_iter().collect();
+    let mut result = Vec::new();
+    let mut index = 0;
+    while index < tokens.len() {
+        match &tokens[index] {
+            TokenTree::Group(group) => {
+                let mut rewritten = Group::new(group.delimiter(), rewrite_effects(group.stream()));
+                rewritten.set_span(group.span());
+                result.push(TokenTree::Group(rewritten));
+                index += 1;
+            }
+            TokenTree::Punct(p)
+                if p.as_char() == '@'
+                    && matches!(tokens.get(index + 1), Some(TokenTree::Ident(i)) if i == "load_with") =>
+            {
+                result.extend(tokens[index..index + 2].iter().cloned());
+                index += 2;
+                if let Some(len) = load_with_args(&tokens[index..]) {
+                    let args = tokens[index..index + len].iter().cloned().collect();
+                    result.push(TokenTree::Group(Group::new(Delimiter::Parenthesis, args)));
+                    index += len;
+                }
+            }
+            tt => {
+                result.push(tt.clone());
+                index += 1;
+            }
+        }
+    }
+    result.into_iter().collect()
+}
+
+/// Returns the number of tokens in `"path", |s| ..` if followed by a closure,
+/// the closure ends at the next top level comma.
+fn load_with_args(tokens: &[TokenTree]) -> Option<usize> {
+    let comma = tokens.iter().position(|tt| is_punct(tt, ','))?;
+    if comma == 0 {
+        return None;
+    }
+    let mut index = comma + 1;
+    if matches!(tokens.get(index), Some(TokenTree::Ident(i)) if i == "move") {
+        index += 1;
+    }
+    if !is_punct(tokens.get(index)?, '|') {
+        return None;
+    }
+    index += 1;
+    index += tokens[index..].iter().position(|tt| is_punct(tt, '|'))? + 1;
+    Some(
+        tokens[index..]
+            .iter()
+            .position(|tt| is_punct(tt, ','))
+            .map_or(tokens.len(), |len| index + len),
+    )
+}
+
+/// Effects that insert components after spawning outside of an asset server scope.
+const ASSET_EFFECTS: &[&str] = &[
+    "asset",
+    "load",
+    "load_with",
+    "load_all",
+    "load_glob",
+    "load_folder",
+];
+
+/// Returns true if `tokens` contain an effect like `@asset` or `@load`.
+pub(crate) fn has_asset_effects(tokens: TokenStream) -> bool {
+    let mut after_at = false;
+    for tt in tokens {
+        match &tt {
+            TokenTree::Group(group) if has_asset_effects(group.stream()) => return true,
+            TokenTree::Ident(ident) if after_at && ASSET_EFFECTS.iter().any(|x| ident == x) => {
+                return true
+            }
+            _ => (),
+        }
+        after_at = is_punct(&tt, '@');
+    }
+    false
+}
@@ -0,0 +1,81 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{spanned::Spanned, DeriveInput, Member, Path, Type};
+
+use crate::bundle_tuple;
+
+/// Parsed `#[bundle(..)]` attributes of a field.
+#[derive(Default)]
+struct FieldConversion {
+    skip: bool,
+    into: Option<Type>,
+    with: Option<Path>,
+}
+
+fn field_conversion(field: &syn::Field) -> syn::Result<FieldConversion> {
+    let mut result = FieldConversion::default();
+    for attr in &field.attrs {
+        if !attr.path().is_ident("bundle") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("component") {
+                Ok(())
+            } else if meta.path.is_ident("skip") {
+                result.skip = true;
+                Ok(())
+            } else if meta.path.is_ident("into") {
+                result.into = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("with") {
+                result.with = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("Expected `component`, `into`, `with` or `skip`."))
+            }
+        })?;
+    }
+    Ok(result)
+}
+
+pub(crate) fn derive_into_bundle(
+    bevy_spawn_fn: TokenStream,
+    input: DeriveInput,
+) -> syn::Result<TokenStream> {
+    let syn::Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new(input.span(), "Expected struct."));
+    };
+
+    let mut bundles = Vec::new();
+
+    for (index, field) in data.fields.iter().enumerate() {
+        let member = match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(index.into()),
+        };
+        let conversion = field_conversion(field)?;
+        if conversion.skip {
+            continue;
+        }
+        let mut expr = quote! {self.#member};
+        if let Some(with) = conversion.with {
+            expr = quote! {#with(#expr)};
+        }
+        if let Some(into) = conversion.into {
+            expr = quote! {#bevy_spawn_fn::InferInto::<#into, _>::into(#expr)};
+        }
+        bundles.push(expr);
+    }
+
+    let bundle = bundle_tuple(bundles);
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics #bevy_spawn_fn::IntoBundle for #name #ty_generics #where_clause {
+            fn into_bundle(self) -> impl #bevy_spawn_fn::Bundle {
+                #bundle
+            }
+        }
+    })
+}
@@ -1,8 +1,34 @@
+#![cfg_attr(feature = "nightly", feature(proc_macro_tracked_path))]
+
 use proc_macro::TokenStream as TokenStream1;
-use proc_macro2::{Ident, TokenStream};
+use proc_macro2::{Ident, TokenStream, TokenTree};
 use proc_macro_crate::FoundCrate;
 use quote::{format_ident, quote, ToTokens};
-use syn::{parse_quote, ItemFn};
+use syn::{
+    ext::IdentExt, meta::ParseNestedMeta, parse::Parser, parse_macro_input, parse_quote,
+    DeriveInput, FnArg, GenericArgument, ItemFn, LitStr, Pat, PatType, PathArguments, Signature,
+    Token, TraitItemFn, Type, TypePath,
+};
+
+mod assets;
+mod effects;
+mod into_bundle;
+mod spawnable;
+
+fn bevy_spawn_fn_path() -> Option<TokenStream> {
+    match proc_macro_crate::crate_name("bevy_spawn_fn") {
+        Ok(FoundCrate::Itself) => Some(quote! {crate}),
+        Ok(FoundCrate::Name(name)) => Some(format_ident!("{name}").into_token_stream()),
+        Err(_) => None,
+    }
+}
+
+/// Create a tuple bundle, nested if exceeds the maximum tuple size of `Bundle`.
+fn bundle_tuple(items: impl IntoIterator<Item = TokenStream>) -> TokenStream {
+    let items = items.into_iter().collect::<Vec<_>>();
+    let chunks = items.chunks(15).map(|chunk| quote! {(#(#chunk,)*)});
+    quote! {(#(#chunks,)*)}
+}
 
 /// Make a function support the `spawn!` macro.
 ///
@@ -12,91 +38,460 @@ use syn::{parse_quote, ItemFn};
 ///
 /// Accepts `&mut World`, `&mut Commands`, `&mut ChildBuilder`, `&mut WorldChildBuilder`.
 ///
+/// If not found, `self` is used as the spawner in methods, this works in `impl` blocks
+/// and default bodies in `trait` blocks.
+///
 /// * Field `asset_server` will be used to enable `@asset` and `@load`.
 ///
-/// Accepts `AssetServer`.
+/// Accepts `AssetServer`, `&AssetServer` and `Res<AssetServer>`.
+/// If no field is named `asset_server`, a field of these types is used instead.
+///
+/// # Arguments
+///
+/// Names of these fields can be changed via `#[spawner_fn(spawner = commands, asset_server = assets)]`,
+/// `spawner = self` is also allowed.
+///
+/// `#[spawner_fn(check_assets)]` checks literal paths in `@load`, `@load_with`, `@load_all` and
+/// `@load_folder` against the `assets` folder at compile time, the folder is found via
+/// `BEVY_ASSET_ROOT` or `CARGO_MANIFEST_DIR`, use `check_assets = "dir"` for another folder.
+/// With feature `nightly`, checked paths and their directories are tracked for recompilation,
+/// otherwise add `println!("cargo:rerun-if-changed=assets")` to a build script.
+///
+/// `#[spawner_fn(manifest)]` generates a module with the same name as the function,
+/// containing the literal paths as `ASSETS`, which can be loaded by `PreloadPlugin`.
 #[proc_macro_attribute]
-pub fn spawner_fn(_: TokenStream1, tokens: TokenStream1) -> TokenStream1 {
-    spawner_fn2(tokens.into()).into()
+pub fn spawner_fn(attr: TokenStream1, tokens: TokenStream1) -> TokenStream1 {
+    spawner_fn2(attr.into(), tokens.into()).into()
 }
 
-fn spawner_fn2(tokens: TokenStream) -> TokenStream {
+/// Arguments of `#[spawner_fn]`.
+#[derive(Default)]
+struct SpawnerFnArgs {
+    spawner: Option<Ident>,
+    asset_server: Option<Ident>,
+    /// `Some` if enabled, with the asset folder if specified.
+    check_assets: Option<Option<LitStr>>,
+    manifest: bool,
+}
+
+impl SpawnerFnArgs {
+    fn parse(attr: TokenStream) -> syn::Result<Self> {
+        let mut args = SpawnerFnArgs::default();
+        let parser = syn::meta::parser(|meta| {
+            if meta.path.is_ident("spawner") {
+                args.spawner = Some(meta.value()?.call(Ident::parse_any)?);
+                Ok(())
+            } else if meta.path.is_ident("asset_server") {
+                args.asset_server = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("check_assets") {
+                args.check_assets = Some(parse_check_assets(&meta)?);
+                Ok(())
+            } else if meta.path.is_ident("manifest") {
+                args.manifest = true;
+                Ok(())
+            } else {
+                Err(meta.error("Expected `spawner`, `asset_server`, `check_assets` or `manifest`."))
+            }
+        });
+        Parser::parse2(parser, attr)?;
+        Ok(args)
+    }
+}
+
+/// Returns the type of a parameter if it is a simple identifier or `self`.
+fn find_param<'t>(sig: &'t Signature, name: &Ident) -> Option<&'t Type> {
+    sig.inputs.iter().find_map(|arg| match arg {
+        FnArg::Typed(PatType { pat, ty, .. }) => match pat.as_ref() {
+            Pat::Ident(pat) if &pat.ident == name => Some(ty.as_ref()),
+            _ => None,
+        },
+        FnArg::Receiver(receiver) if name == "self" => Some(receiver.ty.as_ref()),
+        FnArg::Receiver(_) => None,
+    })
+}
+
+/// Returns true if type is `AssetServer`, `&AssetServer` or `Res<AssetServer>`.
+fn is_asset_server(ty: &Type) -> bool {
+    match ty {
+        Type::Reference(ty) => is_asset_server(&ty.elem),
+        Type::Paren(ty) => is_asset_server(&ty.elem),
+        Type::Path(TypePath { path, .. }) => {
+            let Some(segment) = path.segments.last() else {
+                return false;
+            };
+            if segment.ident == "AssetServer" {
+                return true;
+            }
+            if segment.ident != "Res" {
+                return false;
+            }
+            let PathArguments::AngleBracketed(args) = &segment.arguments else {
+                return false;
+            };
+            args.args.iter().any(|arg| match arg {
+                GenericArgument::Type(ty) => is_asset_server(ty),
+                _ => false,
+            })
+        }
+        _ => false,
+    }
+}
+
+fn spawner_fn2(attr: TokenStream, tokens: TokenStream) -> TokenStream {
     let Ok(mut f) = syn::parse2::<ItemFn>(tokens.clone()) else {
+        if let Ok(f) = syn::parse2::<TraitItemFn>(tokens.clone()) {
+            let e = syn::Error::new(f.sig.ident.span(), "Expected a function body.")
+                .into_compile_error();
+            return quote! {#tokens #e};
+        }
         return quote! {#tokens compile_error!("Expected function.")};
     };
 
-    let bevy_spawn_fn = match proc_macro_crate::crate_name("bevy_spawn_fn") {
-        Ok(FoundCrate::Itself) => {
-            quote! {crate}
+    let Some(bevy_spawn_fn) = bevy_spawn_fn_path() else {
+        return quote! {#tokens compile_error!("Expected crate bevy_spawn_fn.");};
+    };
+
+    let args = match SpawnerFnArgs::parse(attr) {
+        Ok(args) => args,
+        Err(e) => {
+            let e = e.into_compile_error();
+            return quote! {#tokens #e};
         }
-        Ok(FoundCrate::Name(name)) => format_ident!("{name}").into_token_stream(),
-        Err(_) => return quote! {#tokens compile_error!("Expected crate bevy_spawn_fn.")},
     };
 
-    let mut asset_server_found = false;
-
-    for item in &f.sig.inputs {
-        match item {
-            syn::FnArg::Receiver(_) => (),
-            syn::FnArg::Typed(pat_type) => {
-                if syn::parse2::<Ident>(pat_type.pat.to_token_stream())
-                    .is_ok_and(|x| x == format_ident!("asset_server"))
-                {
-                    asset_server_found = true
-                }
+    let spawner_name = args.spawner.unwrap_or_else(|| {
+        let spawner = Ident::new("spawner", f.sig.ident.span());
+        match f.sig.receiver() {
+            Some(receiver) if find_param(&f.sig, &spawner).is_none() => {
+                Ident::new("self", receiver.self_token.span)
             }
+            _ => spawner,
         }
-    }
+    });
+
+    let spawner = match find_param(&f.sig, &spawner_name) {
+        Some(Type::Reference(_)) => quote! {#spawner_name},
+        Some(_) => quote! {&mut #spawner_name},
+        None => {
+            let e = syn::Error::new(
+                spawner_name.span(),
+                format!("Expected a parameter named `{spawner_name}`, specify the spawner with `#[spawner_fn(spawner = name)]`."),
+            )
+            .into_compile_error();
+            return quote! {#tokens #e};
+        }
+    };
 
+    let asset_server = match args.asset_server {
+        Some(name) => {
+            if find_param(&f.sig, &name).is_none() {
+                let e =
+                    syn::Error::new(name.span(), format!("Expected a parameter named `{name}`."))
+                        .into_compile_error();
+                return quote! {#tokens #e};
+            }
+            Some(name)
+        }
+        None => {
+            let name = format_ident!("asset_server");
+            if find_param(&f.sig, &name).is_some() {
+                Some(name)
+            } else {
+                f.sig.inputs.iter().find_map(|arg| match arg {
+                    FnArg::Typed(PatType { pat, ty, .. }) if is_asset_server(ty) => {
+                        match pat.as_ref() {
+                            Pat::Ident(pat) => Some(pat.ident.clone()),
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                })
+            }
+        }
+    };
+
+    let mut errors = check_assets(&args.check_assets, &f);
+    if args.manifest {
+        errors.extend(manifest(&f));
+    }
     let block = f.block;
 
-    if asset_server_found {
+    if let Some(asset_server) = asset_server {
         f.block = parse_quote!({
-            #bevy_spawn_fn::spawner_scope(spawner, || {
-                #bevy_spawn_fn::asset_server_scope(&asset_server, || #block)
+            #bevy_spawn_fn::spawner_scope(#spawner, || {
+                #bevy_spawn_fn::asset_server_scope(&#asset_server, || #block)
             })
         });
     } else {
         f.block = parse_quote!({
-            #bevy_spawn_fn::spawner_scope(spawner, || #block)
+            #bevy_spawn_fn::spawner_scope(#spawner, || #block)
         });
     }
 
-    f.to_token_stream()
+    quote! {#f #errors}
+}
+
+/// Generate a module with the same name as the function,
+/// containing literal paths in `@load`, `@load_with` and `@load_all` as `ASSETS`.
+fn manifest(f: &ItemFn) -> TokenStream {
+    if let Some(receiver) = f.sig.receiver() {
+        return syn::Error::new(
+            receiver.self_token.span,
+            "`manifest` is not supported on methods.",
+        )
+        .into_compile_error();
+    }
+    let mut paths = Vec::new();
+    for path in assets::load_paths(f.block.to_token_stream()) {
+        let value = path.path.value();
+        if path.kind == assets::PathKind::File && !paths.contains(&value) {
+            paths.push(value);
+        }
+    }
+    let vis = &f.vis;
+    let name = &f.sig.ident;
+    let doc = format!("Asset manifest of function `{name}`.");
+    quote! {
+        #[doc = #doc]
+        #vis mod #name {
+            /// Literal asset paths loaded in this function.
+            pub const ASSETS: &[&str] = &[#(#paths),*];
+        }
+    }
+}
+
+/// Check literal asset paths if enabled by argument `check_assets`.
+fn check_assets(check: &Option<Option<LitStr>>, f: &ItemFn) -> TokenStream {
+    match check {
+        Some(dir) => assets::check_assets(f.block.to_token_stream(), dir.as_ref()),
+        None => TokenStream::new(),
+    }
+}
+
+/// Parse `check_assets` or `check_assets = "dir"`.
+fn parse_check_assets(meta: &ParseNestedMeta) -> syn::Result<Option<LitStr>> {
+    if meta.input.peek(Token![=]) {
+        Ok(Some(meta.value()?.parse()?))
+    } else {
+        Ok(None)
+    }
 }
 
 /// Make a system function support the `spawn!` macro.
+///
+/// The `AssetServer` is optional, `@asset` and `@load` can only be used if it exists.
+///
+/// # Exclusive Systems
+///
+/// If a parameter of type `&mut World` is found or `#[spawner_system(exclusive)]` is specified,
+/// entities are spawned directly into the `World` and the `AssetServer` is fetched from the `World`.
+///
+/// Since the `World` is borrowed by the spawner, use `with_world` to access it in the function body.
+///
+/// # Checking Assets
+///
+/// `#[spawner_system(check_assets)]` checks literal asset paths at compile time,
+/// and `#[spawner_system(manifest)]` generates an asset manifest, see `spawner_fn`.
 #[proc_macro_attribute]
-pub fn spawner_system(_: TokenStream1, tokens: TokenStream1) -> TokenStream1 {
-    spawner_system2(tokens.into()).into()
+pub fn spawner_system(attr: TokenStream1, tokens: TokenStream1) -> TokenStream1 {
+    spawner_system2(attr.into(), tokens.into()).into()
+}
+
+/// Arguments of `#[spawner_system]`.
+#[derive(Default)]
+struct SpawnerSystemArgs {
+    exclusive: bool,
+    /// `Some` if enabled, with the asset folder if specified.
+    check_assets: Option<Option<LitStr>>,
+    manifest: bool,
+}
+
+impl SpawnerSystemArgs {
+    fn parse(attr: TokenStream) -> syn::Result<Self> {
+        let mut args = SpawnerSystemArgs::default();
+        let parser = syn::meta::parser(|meta| {
+            if meta.path.is_ident("exclusive") {
+                args.exclusive = true;
+                Ok(())
+            } else if meta.path.is_ident("check_assets") {
+                args.check_assets = Some(parse_check_assets(&meta)?);
+                Ok(())
+            } else if meta.path.is_ident("manifest") {
+                args.manifest = true;
+                Ok(())
+            } else {
+                Err(meta.error("Expected `exclusive`, `check_assets` or `manifest`."))
+            }
+        });
+        Parser::parse2(parser, attr)?;
+        Ok(args)
+    }
+}
+
+/// Returns true if type is `&mut World`.
+fn is_world_mut(ty: &Type) -> bool {
+    match ty {
+        Type::Reference(ty) if ty.mutability.is_some() => match ty.elem.as_ref() {
+            Type::Path(TypePath { path, .. }) => {
+                path.segments.last().is_some_and(|x| x.ident == "World")
+            }
+            _ => false,
+        },
+        Type::Paren(ty) => is_world_mut(&ty.elem),
+        _ => false,
+    }
 }
 
-fn spawner_system2(tokens: TokenStream) -> TokenStream {
+fn spawner_system2(attr: TokenStream, tokens: TokenStream) -> TokenStream {
     let Ok(mut f) = syn::parse2::<ItemFn>(tokens.clone()) else {
         return quote! {#tokens compile_error!("Expected function.")};
     };
 
-    let bevy_spawn_fn = match proc_macro_crate::crate_name("bevy_spawn_fn") {
-        Ok(FoundCrate::Itself) => {
-            quote! {crate}
+    let Some(bevy_spawn_fn) = bevy_spawn_fn_path() else {
+        return quote! {#tokens compile_error!("Expected crate bevy_spawn_fn.");};
+    };
+
+    let args = match SpawnerSystemArgs::parse(attr) {
+        Ok(args) => args,
+        Err(e) => {
+            let e = e.into_compile_error();
+            return quote! {#tokens #e};
+        }
+    };
+
+    let world = f.sig.inputs.iter_mut().find_map(|arg| match arg {
+        FnArg::Typed(PatType { pat, ty, .. }) if is_world_mut(ty) => match pat.as_ref() {
+            Pat::Ident(pat) => Some(pat.ident.clone()),
+            _ => {
+                *pat = parse_quote!(__spawn_world);
+                Some(format_ident!("__spawn_world"))
+            }
+        },
+        _ => None,
+    });
+
+    let world = match world {
+        Some(world) => Some(world),
+        None if args.exclusive => {
+            f.sig
+                .inputs
+                .insert(0, parse_quote!(__spawn_world: &mut #bevy_spawn_fn::World));
+            Some(format_ident!("__spawn_world"))
         }
-        Ok(FoundCrate::Name(name)) => format_ident!("{name}").into_token_stream(),
-        Err(_) => return quote! {#tokens compile_error!("Expected crate bevy_spawn_fn.")},
+        None => None,
     };
 
+    let mut errors = check_assets(&args.check_assets, &f);
+    if args.manifest {
+        errors.extend(manifest(&f));
+    }
+    let block = f.block;
+
+    if let Some(world) = world {
+        f.block = parse_quote!({
+            let __spawn_asset_server = #world.get_resource::<#bevy_spawn_fn::AssetServer>().cloned();
+            #bevy_spawn_fn::spawner_scope(#world, || {
+                #bevy_spawn_fn::optional_asset_server_scope(__spawn_asset_server.as_ref(), || #block)
+            })
+        });
+        return quote! {#f #errors};
+    }
+
     f.sig.inputs.push(parse_quote!(
         mut __spawn_commands: #bevy_spawn_fn::Commands
     ));
     f.sig.inputs.push(parse_quote!(
-        __spawn_asset_server: #bevy_spawn_fn::Res<#bevy_spawn_fn::AssetServer>
+        __spawn_asset_server: ::core::option::Option<#bevy_spawn_fn::Res<#bevy_spawn_fn::AssetServer>>
     ));
 
-    let block = f.block;
-
     f.block = parse_quote!({
         #bevy_spawn_fn::spawner_scope(&mut __spawn_commands, || {
-            #bevy_spawn_fn::asset_server_scope(&__spawn_asset_server, || #block)
+            #bevy_spawn_fn::optional_asset_server_scope(__spawn_asset_server.as_deref(), || #block)
         })
     });
-    f.to_token_stream()
+    quote! {#f #errors}
+}
+
+/// Invoke `$mac! { .. }` with effects rewritten to a form
+/// accepted by `meta_default_constructor`, used in `spawn!`.
+///
+/// ```ignore
+/// __construct! { [$mac] [$converter] Type { .. } }
+/// ```
+#[doc(hidden)]
+#[proc_macro]
+pub fn __construct(tokens: TokenStream1) -> TokenStream1 {
+    let mut tokens = TokenStream::from(tokens).into_iter();
+    let Some(proc_macro2::TokenTree::Group(mac)) = tokens.next() else {
+        return quote! {compile_error!("Expected `[macro]`.")}.into();
+    };
+    let mac = mac.stream();
+    let body = effects::rewrite_effects(tokens.collect());
+    quote! {#mac! { #body }}.into()
+}
+
+/// Expands to `$then` if `$tokens` contain asset effects like `@asset` or `@load`,
+/// `$else` otherwise, used in `spawn!` to only collect deferred components when needed.
+///
+/// ```ignore
+/// __if_asset_effects! { [$tokens] { $then } else { $else } }
+/// ```
+#[doc(hidden)]
+#[proc_macro]
+pub fn __if_asset_effects(tokens: TokenStream1) -> TokenStream1 {
+    let tokens: Vec<_> = TokenStream::from(tokens).into_iter().collect();
+    let [TokenTree::Group(input), TokenTree::Group(then), TokenTree::Ident(_), TokenTree::Group(otherwise)] =
+        tokens.as_slice()
+    else {
+        return quote! {compile_error!("Expected `[tokens] { then } else { else }`.")}.into();
+    };
+    if effects::has_asset_effects(input.stream()) {
+        TokenTree::Group(then.clone()).into_token_stream().into()
+    } else {
+        TokenTree::Group(otherwise.clone())
+            .into_token_stream()
+            .into()
+    }
+}
+
+/// Derive `Spawnable` for a struct.
+///
+/// # Attributes
+///
+/// * `#[spawnable(bundle)]`: The default, field is a `Bundle` added in `into_bundle`.
+/// * `#[spawnable(optional)]`: Field is an `Option<impl Bundle>`, inserted in `spawn_mut` if `Some`.
+/// * `#[spawnable(children)]`: Field is a `Default + IntoIterator<Item: IntoSpawnable>`,
+///   each item is spawned as a child.
+/// * `#[spawnable(skip)]`: Field is ignored.
+#[proc_macro_derive(Spawnable, attributes(spawnable))]
+pub fn derive_spawnable(tokens: TokenStream1) -> TokenStream1 {
+    let input = parse_macro_input!(tokens as DeriveInput);
+    let Some(bevy_spawn_fn) = bevy_spawn_fn_path() else {
+        return quote! {compile_error!("Expected crate bevy_spawn_fn.");}.into();
+    };
+    spawnable::derive_spawnable(bevy_spawn_fn, input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derive `IntoBundle` for a struct.
+///
+/// # Attributes
+///
+/// * `#[bundle(component)]`: The default, field is a `Bundle` and added as is.
+/// * `#[bundle(into = Type)]`: Field is converted to `Type` via `InferInto`.
+/// * `#[bundle(with = path::to::fn)]`: Field is converted by calling a function.
+/// * `#[bundle(skip)]`: Field is ignored.
+///
+/// `with` and `into` can be combined, in which case `with` is called first.
+#[proc_macro_derive(IntoBundle, attributes(bundle))]
+pub fn derive_into_bundle(tokens: TokenStream1) -> TokenStream1 {
+    let input = parse_macro_input!(tokens as DeriveInput);
+    let Some(bevy_spawn_fn) = bevy_spawn_fn_path() else {
+        return quote! {compile_error!("Expected crate bevy_spawn_fn.");}.into();
+    };
+    into_bundle::derive_into_bundle(bevy_spawn_fn, input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
 }
@@ -0,0 +1,137 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{spanned::Spanned, DeriveInput, Member};
+
+use crate::bundle_tuple;
+
+/// Maximum number of `#[spawnable(optional)]` fields spawned together with the static bundle.
+const MAX_COMBINED: usize = 4;
+
+/// How a field participates in `Spawnable`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum FieldKind {
+    Bundle,
+    Optional,
+    Children,
+    Skip,
+}
+
+fn field_kind(field: &syn::Field) -> syn::Result<FieldKind> {
+    let mut kind = None;
+    for attr in &field.attrs {
+        if !attr.path().is_ident("spawnable") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            let parsed = if meta.path.is_ident("bundle") {
+                FieldKind::Bundle
+            } else if meta.path.is_ident("optional") {
+                FieldKind::Optional
+            } else if meta.path.is_ident("children") {
+                FieldKind::Children
+            } else if meta.path.is_ident("skip") {
+                FieldKind::Skip
+            } else {
+                return Err(meta.error("Expected `bundle`, `optional`, `children` or `skip`."));
+            };
+            if kind.replace(parsed).is_some() {
+                return Err(meta.error("Conflicting `spawnable` attributes."));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(kind.unwrap_or(FieldKind::Bundle))
+}
+
+pub(crate) fn derive_spawnable(
+    bevy_spawn_fn: TokenStream,
+    input: DeriveInput,
+) -> syn::Result<TokenStream> {
+    let syn::Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new(input.span(), "Expected struct."));
+    };
+
+    let mut bundles = Vec::new();
+    let mut optionals = Vec::new();
+    let mut children = Vec::new();
+
+    for (index, field) in data.fields.iter().enumerate() {
+        let member = match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(index.into()),
+        };
+        match field_kind(field)? {
+            FieldKind::Bundle => bundles.push(member),
+            FieldKind::Optional => optionals.push(member),
+            FieldKind::Children => children.push(member),
+            FieldKind::Skip => (),
+        }
+    }
+
+    let bundle = bundle_tuple(bundles.iter().map(|member| quote! {self.#member}));
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let spawn_mut = if optionals.is_empty() {
+        TokenStream::new()
+    } else {
+        // Each combined optional doubles the code size, insert the rest afterwards.
+        let (combined, inserted) = optionals.split_at(optionals.len().min(MAX_COMBINED));
+        let spawn = quote! {
+            let mut entity_mut = spawner.spawn_bundle(bundle);
+            #(
+                if let ::core::option::Option::Some(item) = self.#inserted {
+                    entity_mut.insert(item);
+                }
+            )*
+            entity_mut
+        };
+        let body = combined.iter().rev().fold(spawn, |rest, member| {
+            quote! {
+                if let ::core::option::Option::Some(item) = self.#member {
+                    let bundle = (bundle, item);
+                    #rest
+                } else {
+                    #rest
+                }
+            }
+        });
+        quote! {
+            fn spawn_mut<'t>(
+                self,
+                spawner: &'t mut #bevy_spawn_fn::Spawner
+            ) -> #bevy_spawn_fn::EntityMutSpawner<'t> {
+                let bundle = #bundle;
+                #body
+            }
+        }
+    };
+
+    let spawn_children = if children.is_empty() {
+        TokenStream::new()
+    } else {
+        quote! {
+            fn spawn_children(&mut self, spawner: &mut #bevy_spawn_fn::Spawner) {
+                #(
+                    for item in ::core::mem::take(&mut self.#children) {
+                        spawner.spawn(item);
+                    }
+                )*
+            }
+        }
+    };
+
+    let is_static = optionals.is_empty() && children.is_empty();
+
+    Ok(quote! {
+        impl #impl_generics #bevy_spawn_fn::Spawnable for #name #ty_generics #where_clause {
+            const IS_STATIC: bool = #is_static;
+            fn into_bundle(self) -> impl #bevy_spawn_fn::Bundle {
+                #bundle
+            }
+            #spawn_mut
+            #spawn_children
+        }
+    })
+}
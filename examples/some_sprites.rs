@@ -11,7 +11,6 @@ use bevy::{
 };
 use bevy_asset::Handle;
 use bevy_spawn_fn::*;
-use default_constructor::infer_construct;
 
 pub fn main() {
     App::new()
@@ -40,18 +39,16 @@ impl Spawnable for ManySprites {
         }
     }
 
-    fn spawn_children(&mut self, spawner: &mut Spawner) {
+    fn spawn_children(&mut self, _: &mut Spawner) {
         for pos in self.positions.iter().copied() {
-            spawner.spawn_bundle(infer_construct! {
-                SpriteBundle {
-                    sprite: Sprite {
-                        color: self.color,
-                        custom_size: @some self.size,
-                    },
-                    texture: self.texture.clone(),
-                    transform: Transform {
-                        translation: pos
-                    }
+            spawn!(SpriteBundle {
+                sprite: Sprite {
+                    color: self.color,
+                    custom_size: @some self.size,
+                },
+                texture: self.texture.clone(),
+                transform: Transform {
+                    translation: pos
                 }
             });
         }
@@ -59,7 +56,7 @@ impl Spawnable for ManySprites {
 }
 
 #[spawner_system]
-fn startup() {
+fn startup(asset_server: Res<AssetServer>) {
     spawn!(Camera2dBundle);
 
     spawn!(SpriteBundle {
@@ -78,7 +75,7 @@ fn startup() {
             color: Color::srgb(2., 2., 2.),
             custom_size: @some [64., 64.],
         },
-        (__spawn_asset_server.load::<Image>("circle.png")),
+        (asset_server.load::<Image>("circle.png")),
         TransformBundle {
             local: Transform {
                 translation: [-20., 0., 0.]
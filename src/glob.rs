@@ -0,0 +1,94 @@
+use bevy_asset::{io::ErasedAssetReader, AssetPath, AssetServer};
+use bevy_tasks::{block_on, futures_lite::StreamExt};
+use std::path::{Path, PathBuf};
+
+/// Returns true if a path segment matches a pattern segment with `*` and `?` wildcards.
+fn matches_segment(pattern: &[u8], name: &[u8]) -> bool {
+    match (pattern.split_first(), name.split_first()) {
+        (None, None) => true,
+        (Some((b'*', rest)), _) => {
+            matches_segment(rest, name)
+                || name
+                    .split_first()
+                    .is_some_and(|(_, name)| matches_segment(pattern, name))
+        }
+        (Some((b'?', rest)), Some((_, name))) => matches_segment(rest, name),
+        (Some((p, rest)), Some((n, name))) if p == n => matches_segment(rest, name),
+        _ => false,
+    }
+}
+
+/// Entries of a directory, empty if not found.
+fn read_dir(reader: &dyn ErasedAssetReader, path: &Path) -> Vec<PathBuf> {
+    block_on(async {
+        match reader.read_directory(path).await {
+            Ok(entries) => entries.collect().await,
+            Err(_) => Vec::new(),
+        }
+    })
+}
+
+fn is_dir(reader: &dyn ErasedAssetReader, path: &Path) -> bool {
+    block_on(reader.is_directory(path)).unwrap_or(false)
+}
+
+/// Recursively collect paths in `dir` matching the remaining pattern segments.
+fn collect(
+    reader: &dyn ErasedAssetReader,
+    dir: &Path,
+    segments: &[&str],
+    result: &mut Vec<PathBuf>,
+) {
+    let Some((segment, rest)) = segments.split_first() else {
+        return;
+    };
+    if *segment == "**" {
+        collect(reader, dir, rest, result);
+    }
+    for path in read_dir(reader, dir) {
+        let Some(name) = path.file_name().and_then(|x| x.to_str()) else {
+            continue;
+        };
+        if *segment == "**" {
+            if is_dir(reader, &path) {
+                collect(reader, &path, segments, result);
+            }
+        } else if matches_segment(segment.as_bytes(), name.as_bytes()) {
+            if rest.is_empty() {
+                if !is_dir(reader, &path) {
+                    result.push(path);
+                }
+            } else if is_dir(reader, &path) {
+                collect(reader, &path, rest, result);
+            }
+        }
+    }
+}
+
+/// Find asset paths matching a glob pattern with the [`AssetReader`](bevy_asset::io::AssetReader)
+/// of its asset source, supports `*`, `?` and `**`. Results are sorted.
+///
+/// This blocks on reading directories, and returns nothing if the source cannot read directories.
+pub(crate) fn glob_asset_paths(
+    asset_server: &AssetServer,
+    pattern: &str,
+) -> Vec<AssetPath<'static>> {
+    let Ok(pattern) = AssetPath::try_parse(pattern) else {
+        return Vec::new();
+    };
+    let Ok(source) = asset_server.get_source(pattern.source().clone_owned()) else {
+        return Vec::new();
+    };
+    let Some(path) = pattern.path().to_str() else {
+        return Vec::new();
+    };
+    let segments: Vec<_> = path.split('/').filter(|x| !x.is_empty()).collect();
+    let mut result = Vec::new();
+    collect(source.reader(), Path::new(""), &segments, &mut result);
+    result.sort();
+    result.dedup();
+    result
+        .into_iter()
+        .map(|path| AssetPath::from(path).with_source(pattern.source().clone_owned()))
+        .collect()
+}
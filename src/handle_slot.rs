@@ -0,0 +1,599 @@
+use bevy_asset::{
+    meta::Settings, Asset, AssetHandleProvider, AssetPath, AssetServer, Assets, Handle,
+};
+use bevy_ecs::{
+    component::{Component, ComponentHooks, ComponentId, StorageType},
+    entity::Entity,
+    world::{DeferredWorld, World},
+};
+use bevy_utils::HashMap;
+use std::{
+    any::TypeId, borrow::Cow, fmt::Debug, marker::PhantomData, mem, panic::Location, sync::Arc,
+};
+
+use crate::{defer_insert, no_asset_server, AddMe, LoadAllMe, LoadGlobMe, LoadMe, SpawnError};
+
+/// A type that can be created by the `@asset` and `@load` effects.
+pub trait AssetEffect: Sized {
+    /// Type of the underlying asset.
+    type Asset: Asset;
+
+    /// Create from a [`Handle`] created in an [`asset_server_scope`](crate::asset_server_scope).
+    fn from_handle(handle: Handle<Self::Asset>) -> Self;
+
+    /// Create from an asset outside of an [`asset_server_scope`](crate::asset_server_scope).
+    #[track_caller]
+    fn from_deferred_asset(asset: Self::Asset) -> Self;
+
+    /// Create from a path outside of an [`asset_server_scope`](crate::asset_server_scope).
+    #[track_caller]
+    fn from_deferred_path(path: AssetPath<'static>) -> Self;
+
+    /// Create from a path in a collection like `@load_all` outside of an
+    /// [`asset_server_scope`](crate::asset_server_scope), calls `from_deferred_path` by default.
+    #[track_caller]
+    fn from_deferred_path_in_collection(path: AssetPath<'static>) -> Self {
+        Self::from_deferred_path(path)
+    }
+
+    /// Create from a path and loader settings outside of an [`asset_server_scope`](crate::asset_server_scope).
+    #[track_caller]
+    fn from_deferred_path_with_settings<S: Settings>(
+        path: AssetPath<'static>,
+        settings: impl Fn(&mut S) + Send + Sync + 'static,
+    ) -> Self;
+}
+
+/// Outside of an asset server scope, [`spawn!`](crate::spawn!) inserts [`AddMe`] or [`LoadMe`]
+/// after spawning, this only works if the handle is a component.
+///
+/// # Panics
+///
+/// When inserted, if the spawned entity has no `Handle<T>` component,
+/// i.e. the handle is a field of a component, use [`HandleSlot`] instead.
+/// Collections panic immediately, use [`Handles`] or [`HandleSlot`] instead.
+impl<T: Asset> AssetEffect for Handle<T> {
+    type Asset = T;
+
+    fn from_handle(handle: Handle<T>) -> Self {
+        handle
+    }
+
+    #[track_caller]
+    fn from_deferred_asset(asset: T) -> Self {
+        let check = CheckComponent::<Handle<T>>::new(Location::caller());
+        defer_insert(move |entity| entity.insert((check, AddMe::new(asset))));
+        Handle::default()
+    }
+
+    #[track_caller]
+    fn from_deferred_path(path: AssetPath<'static>) -> Self {
+        let check = CheckComponent::<Handle<T>>::new(Location::caller());
+        defer_insert(move |entity| entity.insert((check, LoadMe::<T>::new(path.to_string()))));
+        Handle::default()
+    }
+
+    /// # Panics
+    ///
+    /// Since a `Vec<Handle<T>>` is not a component, use [`Handles`] or [`HandleSlot`] instead.
+    /// In [`try_spawn!`](crate::try_spawn!) this is returned as [`SpawnError::NoAssetServer`].
+    #[track_caller]
+    fn from_deferred_path_in_collection(_: AssetPath<'static>) -> Self {
+        no_asset_server(Location::caller());
+        Handle::default()
+    }
+
+    #[track_caller]
+    fn from_deferred_path_with_settings<S: Settings>(
+        path: AssetPath<'static>,
+        settings: impl Fn(&mut S) + Send + Sync + 'static,
+    ) -> Self {
+        let check = CheckComponent::<Handle<T>>::new(Location::caller());
+        defer_insert(move |entity| {
+            entity.insert((
+                check,
+                LoadMe::<T>::new_with_settings(path.to_string(), settings),
+            ))
+        });
+        Handle::default()
+    }
+}
+
+/// [`Component`] that immediately removes itself, panics if the entity does not have
+/// the default component `C` replaced by a deferred component like [`LoadMe`].
+struct CheckComponent<C: Component> {
+    location: &'static Location<'static>,
+    p: PhantomData<C>,
+}
+
+impl<C: Component> CheckComponent<C> {
+    fn new(location: &'static Location<'static>) -> Self {
+        CheckComponent {
+            location,
+            p: PhantomData,
+        }
+    }
+}
+
+impl<C: Component> Component for CheckComponent<C> {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+    fn register_component_hooks(hooks: &mut ComponentHooks) {
+        hooks.on_insert(|mut world, entity, _| {
+            let entity_ref = world.entity(entity);
+            let location = entity_ref.get::<Self>().unwrap().location;
+            if !entity_ref.contains::<C>() {
+                panic!("{}", SpawnError::NotAComponent(location))
+            }
+            world.commands().entity(entity).remove::<Self>();
+        });
+    }
+}
+
+/// A collection of handles that can be created by the `@load_all` and `@load_glob` effects.
+pub trait AssetCollection: Sized {
+    /// Type of the underlying asset.
+    type Asset: Asset;
+
+    /// Create from [`Handle`]s created in an [`asset_server_scope`](crate::asset_server_scope).
+    fn from_handles(handles: impl IntoIterator<Item = Handle<Self::Asset>>) -> Self;
+
+    /// Create from paths outside of an [`asset_server_scope`](crate::asset_server_scope).
+    #[track_caller]
+    fn from_deferred_paths(paths: Vec<AssetPath<'static>>) -> Self;
+
+    /// Create from a glob pattern outside of an [`asset_server_scope`](crate::asset_server_scope).
+    #[track_caller]
+    fn from_deferred_glob(pattern: String) -> Self;
+}
+
+impl<T: AssetEffect> AssetCollection for Vec<T> {
+    type Asset = T::Asset;
+
+    fn from_handles(handles: impl IntoIterator<Item = Handle<T::Asset>>) -> Self {
+        handles.into_iter().map(T::from_handle).collect()
+    }
+
+    #[track_caller]
+    fn from_deferred_paths(paths: Vec<AssetPath<'static>>) -> Self {
+        paths
+            .into_iter()
+            .map(|path| T::from_deferred_path_in_collection(path))
+            .collect()
+    }
+
+    /// # Panics
+    ///
+    /// Since matching paths requires an [`AssetServer`], use [`Handles`] instead.
+    /// In [`try_spawn!`](crate::try_spawn!) this is returned as [`SpawnError::NoAssetServer`].
+    #[track_caller]
+    fn from_deferred_glob(_: String) -> Self {
+        no_asset_server(Location::caller());
+        Vec::new()
+    }
+}
+
+/// [`Component`] containing [`Handle`]s created by `@load_all` or `@load_glob`.
+///
+/// Outside of an asset server scope, [`spawn!`](crate::spawn!) inserts [`LoadAllMe`] or
+/// [`LoadGlobMe`] after spawning, which panics if [`Handles`] is not a component.
+#[derive(Debug, Clone)]
+pub struct Handles<T: Asset>(pub Vec<Handle<T>>);
+
+impl<T: Asset> Default for Handles<T> {
+    fn default() -> Self {
+        Handles(Vec::new())
+    }
+}
+
+impl<T: Asset> Component for Handles<T> {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+}
+
+impl<T: Asset> AssetCollection for Handles<T> {
+    type Asset = T;
+
+    fn from_handles(handles: impl IntoIterator<Item = Handle<T>>) -> Self {
+        Handles(handles.into_iter().collect())
+    }
+
+    #[track_caller]
+    fn from_deferred_paths(paths: Vec<AssetPath<'static>>) -> Self {
+        let check = CheckComponent::<Self>::new(Location::caller());
+        defer_insert(move |entity| entity.insert((check, LoadAllMe::<T>::new(paths))));
+        Handles::default()
+    }
+
+    #[track_caller]
+    fn from_deferred_glob(pattern: String) -> Self {
+        let check = CheckComponent::<Self>::new(Location::caller());
+        defer_insert(move |entity| entity.insert((check, LoadGlobMe::<T>::new(pattern))));
+        Handles::default()
+    }
+}
+
+/// Load a path with loader settings from a [`HandleSlot`].
+type SettingsLoader<T> = Arc<dyn Fn(&AssetServer, AssetPath<'static>) -> Handle<T> + Send + Sync>;
+
+/// A [`Handle`] that can be used as a field of a component,
+/// resolved when the component is inserted.
+///
+/// The component should implement [`ResolveHandles`] and
+/// register [`resolve_handles_hook`] as its `on_insert` hook.
+///
+/// ```ignore
+/// pub struct Material {
+///     texture: HandleSlot<Image>,
+/// }
+///
+/// impl ResolveHandles for Material {
+///     fn resolve_handles(&mut self, resolver: &mut HandleResolver) {
+///         self.texture.resolve_handles(resolver);
+///     }
+/// }
+///
+/// impl Component for Material {
+///     const STORAGE_TYPE: StorageType = StorageType::Table;
+///     fn register_component_hooks(hooks: &mut ComponentHooks) {
+///         hooks.on_insert(resolve_handles_hook::<Self>);
+///     }
+/// }
+/// ```
+pub enum HandleSlot<T: Asset> {
+    /// A resolved handle.
+    Handle(Handle<T>),
+    /// An asset to be added to [`Assets<T>`].
+    Asset(T),
+    /// A path to be loaded by the [`AssetServer`].
+    Path(Cow<'static, str>),
+    /// A path to be loaded by the [`AssetServer`] with loader settings.
+    PathWithSettings(Cow<'static, str>, SettingsLoader<T>),
+}
+
+impl<T: Asset + Debug> Debug for HandleSlot<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Handle(handle) => f.debug_tuple("Handle").field(handle).finish(),
+            Self::Asset(asset) => f.debug_tuple("Asset").field(asset).finish(),
+            Self::Path(path) => f.debug_tuple("Path").field(path).finish(),
+            Self::PathWithSettings(path, _) => {
+                f.debug_tuple("PathWithSettings").field(path).finish()
+            }
+        }
+    }
+}
+
+impl<T: Asset> Default for HandleSlot<T> {
+    fn default() -> Self {
+        HandleSlot::Handle(Handle::default())
+    }
+}
+
+impl<T: Asset> Clone for HandleSlot<T>
+where
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        match self {
+            HandleSlot::Handle(handle) => HandleSlot::Handle(handle.clone()),
+            HandleSlot::Asset(asset) => HandleSlot::Asset(asset.clone()),
+            HandleSlot::Path(path) => HandleSlot::Path(path.clone()),
+            HandleSlot::PathWithSettings(path, loader) => {
+                HandleSlot::PathWithSettings(path.clone(), loader.clone())
+            }
+        }
+    }
+}
+
+impl<T: Asset> From<Handle<T>> for HandleSlot<T> {
+    fn from(value: Handle<T>) -> Self {
+        HandleSlot::Handle(value)
+    }
+}
+
+impl<T: Asset> HandleSlot<T> {
+    /// Create a [`HandleSlot`] that loads a path.
+    pub fn load(path: impl Into<Cow<'static, str>>) -> Self {
+        HandleSlot::Path(path.into())
+    }
+
+    /// Create a [`HandleSlot`] that loads a path with loader settings.
+    pub fn load_with_settings<S: Settings>(
+        path: impl Into<Cow<'static, str>>,
+        settings: impl Fn(&mut S) + Send + Sync + 'static,
+    ) -> Self {
+        let settings = Arc::new(settings);
+        HandleSlot::PathWithSettings(
+            path.into(),
+            Arc::new(move |asset_server, path| {
+                let settings = settings.clone();
+                asset_server.load_with_settings(path, move |s: &mut S| settings(s))
+            }),
+        )
+    }
+
+    /// Returns the [`Handle`] if resolved.
+    pub fn handle(&self) -> Option<&Handle<T>> {
+        match self {
+            HandleSlot::Handle(handle) => Some(handle),
+            _ => None,
+        }
+    }
+
+    /// Returns true if resolved.
+    pub fn is_resolved(&self) -> bool {
+        matches!(self, HandleSlot::Handle(_))
+    }
+}
+
+impl<T: Asset> AssetEffect for HandleSlot<T> {
+    type Asset = T;
+
+    fn from_handle(handle: Handle<T>) -> Self {
+        HandleSlot::Handle(handle)
+    }
+
+    fn from_deferred_asset(asset: T) -> Self {
+        HandleSlot::Asset(asset)
+    }
+
+    fn from_deferred_path(path: AssetPath<'static>) -> Self {
+        HandleSlot::Path(Cow::Owned(path.to_string()))
+    }
+
+    fn from_deferred_path_with_settings<S: Settings>(
+        path: AssetPath<'static>,
+        settings: impl Fn(&mut S) + Send + Sync + 'static,
+    ) -> Self {
+        HandleSlot::load_with_settings(path.to_string(), settings)
+    }
+}
+
+/// Resolves [`HandleSlot`]s in [`resolve_handles_hook`].
+///
+/// Assets are added to [`Assets<T>`] and paths are loaded by the [`AssetServer`],
+/// which is only required if an unresolved path is present.
+pub struct HandleResolver {
+    asset_server: Option<AssetServer>,
+    providers: HashMap<TypeId, AssetHandleProvider>,
+    missing: Vec<fn(&World) -> (TypeId, AssetHandleProvider)>,
+    added: Vec<Box<dyn FnOnce(&mut DeferredWorld)>>,
+}
+
+impl HandleResolver {
+    /// Returns the [`AssetServer`] if present.
+    pub fn asset_server(&self) -> Option<&AssetServer> {
+        self.asset_server.as_ref()
+    }
+
+    /// Reserve a handle in [`Assets<T>`] and add the asset after resolving,
+    /// returns the asset if [`Assets<T>`] has not been fetched yet.
+    fn add<T: Asset>(&mut self, asset: T) -> Result<Handle<T>, T> {
+        let Some(provider) = self.providers.get(&TypeId::of::<T>()) else {
+            self.missing.push(|world| {
+                let provider = world.resource::<Assets<T>>().get_handle_provider();
+                (TypeId::of::<T>(), provider)
+            });
+            return Err(asset);
+        };
+        let handle = provider.reserve_handle().typed::<T>();
+        let id = handle.id();
+        self.added.push(Box::new(move |world| {
+            world.resource_mut::<Assets<T>>().insert(id, asset)
+        }));
+        Ok(handle)
+    }
+
+    /// # Panics
+    ///
+    /// If no [`AssetServer`] is present.
+    fn require_asset_server(&self) -> &AssetServer {
+        self.asset_server
+            .as_ref()
+            .expect("Resolving a path in a `HandleSlot` requires an `AssetServer`.")
+    }
+}
+
+/// A type containing [`HandleSlot`]s.
+pub trait ResolveHandles {
+    /// Resolve all [`HandleSlot`]s using a [`HandleResolver`].
+    fn resolve_handles(&mut self, resolver: &mut HandleResolver);
+}
+
+impl<T: Asset> ResolveHandles for HandleSlot<T> {
+    fn resolve_handles(&mut self, resolver: &mut HandleResolver) {
+        *self = match mem::take(self) {
+            HandleSlot::Handle(handle) => HandleSlot::Handle(handle),
+            HandleSlot::Asset(asset) => match resolver.add(asset) {
+                Ok(handle) => HandleSlot::Handle(handle),
+                Err(asset) => HandleSlot::Asset(asset),
+            },
+            HandleSlot::Path(path) => {
+                HandleSlot::Handle(resolver.require_asset_server().load(path.into_owned()))
+            }
+            HandleSlot::PathWithSettings(path, loader) => HandleSlot::Handle(loader(
+                resolver.require_asset_server(),
+                AssetPath::from(path.into_owned()),
+            )),
+        }
+    }
+}
+
+impl<T: ResolveHandles> ResolveHandles for Option<T> {
+    fn resolve_handles(&mut self, resolver: &mut HandleResolver) {
+        if let Some(item) = self {
+            item.resolve_handles(resolver)
+        }
+    }
+}
+
+impl<T: ResolveHandles> ResolveHandles for Vec<T> {
+    fn resolve_handles(&mut self, resolver: &mut HandleResolver) {
+        for item in self {
+            item.resolve_handles(resolver)
+        }
+    }
+}
+
+/// An `on_insert` hook that calls [`ResolveHandles::resolve_handles`] on a component.
+///
+/// Does not require an [`AssetServer`] unless the component has an unresolved path.
+pub fn resolve_handles_hook<C: Component + ResolveHandles>(
+    mut world: DeferredWorld,
+    entity: Entity,
+    _: ComponentId,
+) {
+    let mut resolver = HandleResolver {
+        asset_server: world.get_resource::<AssetServer>().cloned(),
+        providers: HashMap::default(),
+        missing: Vec::new(),
+        added: Vec::new(),
+    };
+    // `Assets<T>` cannot be accessed while the component is borrowed,
+    // so assets of unseen types are resolved in a second pass.
+    loop {
+        let Some(mut component) = world.get_mut::<C>(entity) else {
+            return;
+        };
+        component.resolve_handles(&mut resolver);
+        if resolver.missing.is_empty() {
+            break;
+        }
+        for fetch in mem::take(&mut resolver.missing) {
+            let (type_id, provider) = fetch(&world);
+            resolver.providers.insert(type_id, provider);
+        }
+    }
+    for add in resolver.added {
+        add(&mut world);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy::render::texture::Image;
+    use bevy_asset::{Asset, AssetServer, Assets, Handle, LoadedFolder};
+    use bevy_ecs::{entity::Entity, world::World};
+    use bevy_spawn_fn_derive::spawner_fn;
+
+    use crate::{spawn, spawner_scope, test::*, HandleSlot, Handles};
+
+    #[spawner_fn]
+    fn spawn_material(spawner: &mut World) -> Entity {
+        spawn!(Material {
+            texture: @load "texture.png",
+            layers: vec![HandleSlot::Asset(Image::default()), HandleSlot::load("layer.png")],
+        })
+    }
+
+    #[test]
+    fn handle_slot() {
+        let mut app = test_app();
+        let world = app.world_mut();
+        let entity = spawn_material(world);
+        let material = world.entity(entity).get::<Material>().unwrap();
+        let texture = material.texture.handle().unwrap();
+        assert_eq!(texture.path().unwrap().to_string(), "texture.png");
+        assert!(material.layers.iter().all(HandleSlot::is_resolved));
+        let layer = material.layers[0].handle().unwrap();
+        assert!(layer.is_strong() && layer.path().is_none());
+    }
+
+    #[test]
+    fn handle_slot_headless() {
+        let mut world = World::new();
+        world.init_resource::<Assets<Image>>();
+        let entity = world
+            .spawn(Material {
+                texture: HandleSlot::Asset(Image::default()),
+                layers: vec![
+                    Handle::default().into(),
+                    HandleSlot::Asset(Image::default()),
+                ],
+            })
+            .id();
+        let material = world.entity(entity).get::<Material>().unwrap();
+        assert!(material.layers.iter().all(HandleSlot::is_resolved));
+        let assets = world.resource::<Assets<Image>>();
+        assert!(assets.contains(material.texture.handle().unwrap()));
+        assert!(assets.contains(material.layers[1].handle().unwrap()));
+    }
+
+    #[spawner_fn]
+    fn spawn_collections(spawner: &mut World) -> [Entity; 4] {
+        [
+            spawn!(Material {
+                layers: @load_all ["a.png", "b.png"],
+            }),
+            spawn!(FolderBundle {
+                folder: @load_folder "."
+            }),
+            spawn!(HandlesBundle {
+                handles: @load_all ["one.value", "two.value"],
+            }),
+            spawn!(HandlesBundle {
+                handles: @load_glob "**/*.value",
+            }),
+        ]
+    }
+
+    fn paths<T: Asset>(handles: &[Handle<T>]) -> Vec<String> {
+        handles
+            .iter()
+            .map(|x| x.path().unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn collection_effects() {
+        let mut app = test_app();
+        let world = app.world_mut();
+        let asset_server = world.resource::<AssetServer>().clone();
+        let entity = spawner_scope(world, || {
+            crate::asset_server_scope(&asset_server, || {
+                spawn!(Frames {
+                    frames: @load_all ["a.png", "b.png"],
+                    glob: @load_glob "frames/*.value",
+                })
+            })
+        });
+        let frames = world.entity(entity).get::<Frames>().unwrap();
+        assert_eq!(paths(&frames.frames), ["a.png", "b.png"]);
+        assert_eq!(paths(&frames.glob), ["frames/0.value", "frames/1.value"]);
+
+        let [material, folder, all, glob] = spawn_collections(world);
+        world.flush_commands();
+        let material = world.entity(material).get::<Material>().unwrap();
+        assert_eq!(material.layers.len(), 2);
+        let layer = material.layers[0].handle().unwrap();
+        assert_eq!(layer.path().unwrap().to_string(), "a.png");
+        let folder = world.entity(folder).get::<Handle<LoadedFolder>>().unwrap();
+        assert!(folder.is_strong());
+        let all = world.entity(all).get::<Handles<Value>>().unwrap();
+        assert_eq!(paths(&all.0), ["one.value", "two.value"]);
+        assert!(!world.entity(glob).contains::<crate::LoadGlobMe<Value>>());
+        let glob = world.entity(glob).get::<Handles<Value>>().unwrap();
+        assert_eq!(
+            paths(&glob.0),
+            [
+                "frames/0.value",
+                "frames/1.value",
+                "frames/sub/2.value",
+                "one.value",
+                "two.value"
+            ]
+        );
+    }
+
+    #[spawner_fn]
+    fn spawn_deferred_glob(spawner: &mut World) -> Entity {
+        spawn!(Frames {
+            glob: @load_glob "*.value",
+        })
+    }
+
+    #[test]
+    #[should_panic(expected = "Must be called in an asset server scope")]
+    fn deferred_glob_vec() {
+        spawn_deferred_glob(&mut World::new());
+    }
+}
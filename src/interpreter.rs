@@ -0,0 +1,1450 @@
+use bevy_app::{App, Plugin};
+use bevy_asset::{Asset, AssetServer, Handle, ReflectAsset, ReflectHandle};
+use bevy_ecs::{
+    entity::Entity,
+    reflect::{AppTypeRegistry, ReflectBundle, ReflectComponent},
+    world::World,
+};
+use bevy_reflect::{
+    std_traits::ReflectDefault, DynamicArray, DynamicEnum, DynamicList, DynamicStruct,
+    DynamicTuple, DynamicTupleStruct, DynamicVariant, EnumInfo, FromType, Reflect,
+    ReflectFromReflect, ReflectMut, TypeInfo, TypeRegistration, TypeRegistry, VariantInfo,
+};
+use std::{
+    any::TypeId,
+    borrow::Cow,
+    fmt::{self, Display},
+    marker::PhantomData,
+};
+
+use crate::{glob::glob_asset_paths, PrefabNode};
+
+/// Type data on [`Handle<T>`] that loads an asset by path,
+/// used by `@load`, `@load_all` and `@load_glob` in [`spawn_from_str`].
+///
+/// Register with [`ReflectLoadPlugin<T>`].
+#[derive(Clone)]
+pub struct ReflectLoad {
+    load: fn(&AssetServer, String) -> Box<dyn Reflect>,
+}
+
+impl ReflectLoad {
+    /// Load an asset, returns a [`Handle<T>`].
+    pub fn load(&self, asset_server: &AssetServer, path: impl Into<String>) -> Box<dyn Reflect> {
+        (self.load)(asset_server, path.into())
+    }
+}
+
+impl<T: Asset> FromType<Handle<T>> for ReflectLoad {
+    fn from_type() -> Self {
+        ReflectLoad {
+            load: |asset_server, path| Box::new(asset_server.load::<T>(path)),
+        }
+    }
+}
+
+/// A [`Plugin`] that registers [`Handle<T>`] with [`ReflectLoad`] for reflection.
+pub struct ReflectLoadPlugin<T: Asset>(PhantomData<T>);
+
+impl<T: Asset> Default for ReflectLoadPlugin<T> {
+    fn default() -> Self {
+        ReflectLoadPlugin(PhantomData)
+    }
+}
+
+impl<T: Asset> Plugin for ReflectLoadPlugin<T> {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Handle<T>>()
+            .register_type_data::<Handle<T>, ReflectLoad>();
+    }
+}
+
+/// Error from [`spawn_from_str`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpawnStrError {
+    /// Line of the error, starting from 1.
+    pub line: usize,
+    /// Column of the error, starting from 1.
+    pub column: usize,
+    /// Description of the error.
+    pub message: String,
+}
+
+impl SpawnStrError {
+    fn new(source: &str, failure: Failure) -> Self {
+        let before = &source[..failure.offset.min(source.len())];
+        let line_start = before.rfind('\n').map(|x| x + 1).unwrap_or(0);
+        SpawnStrError {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            message: failure.message,
+        }
+    }
+}
+
+impl Display for SpawnStrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl std::error::Error for SpawnStrError {}
+
+/// Spawn an entity from a string using a subset of the syntax of [`spawn!`](crate::spawn!).
+///
+/// Types are resolved by their full or short type path in the [`AppTypeRegistry`],
+/// the entity and each child is a component, bundle or tuple of them, which must be
+/// registered with `#[reflect(Component)]` or `#[reflect(Bundle)]`.
+/// Omitted fields are taken from `#[reflect(Default)]`.
+///
+/// Supported conversions are numbers between numeric types, strings to [`String`],
+/// values to [`Option`] and `[..]` or `(..)` to lists, arrays, tuples and structs by field order.
+/// Enum variants must be qualified like `Visibility::Hidden`, except `Some` and `None`.
+///
+/// Supported effects are `@some`, `@arr`, `@load`, `@load_all` and `@load_glob`,
+/// which require [`ReflectLoad`], and `@asset`, which requires [`ReflectAsset`].
+/// `@load_with`, user defined effects and arbitrary rust expressions are not supported.
+/// Effects are only performed if the whole string type checks.
+///
+/// Bevy's own bundles like `SpriteBundle` are not reflected, register your own bundle
+/// with `#[reflect(Bundle)]` or spawn components individually.
+///
+/// ```ignore
+/// spawn_from_str(world, r#"
+///     (
+///         Sprite { custom_size: @some [64., 64.] },
+///         Transform { translation: [0, 1, 0] },
+///     ) => [
+///         (Visibility::Hidden, Transform { translation: [1, 0, 0] }),
+///     ]
+/// "#)?;
+/// ```
+pub fn spawn_from_str(world: &mut World, source: &str) -> Result<Entity, SpawnStrError> {
+    let node = (|| {
+        let tokens = tokenize(source)?;
+        let ast = Parser {
+            tokens: &tokens,
+            pos: 0,
+            end: source.len(),
+        }
+        .parse()?;
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        let registry = registry.read();
+        let mut builder = Builder {
+            world,
+            registry: &registry,
+            effects: false,
+        };
+        // Type check first so effects like `@load` and `@asset` do not run on failure.
+        builder.build_node(&ast)?;
+        builder.effects = true;
+        builder.build_node(&ast)
+    })()
+    .map_err(|failure| SpawnStrError::new(source, failure))?;
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let mut entity = world.spawn_empty();
+    node.spawn_on(&mut entity, &registry.read());
+    Ok(entity.id())
+}
+
+struct Failure {
+    offset: usize,
+    message: String,
+}
+
+type Result<T, E = Failure> = std::result::Result<T, E>;
+
+fn fail<T>(offset: usize, message: impl Into<String>) -> Result<T> {
+    Err(Failure {
+        offset,
+        message: message.into(),
+    })
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Int(String),
+    Float(String),
+    Str(String),
+    Char(char),
+    Punct(&'static str),
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Ident(s) | Token::Int(s) | Token::Float(s) => write!(f, "`{s}`"),
+            Token::Str(s) => write!(f, "`{s:?}`"),
+            Token::Char(c) => write!(f, "`{c:?}`"),
+            Token::Punct(p) => write!(f, "`{p}`"),
+        }
+    }
+}
+
+const PUNCTS: &[&str] = &[
+    "::", "=>", "{", "}", "(", ")", "[", "]", ",", ":", "@", "-", "<", ">", ";", "|",
+];
+
+fn tokenize(source: &str) -> Result<Vec<(usize, Token)>> {
+    let bytes = source.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    let take_while = |mut i: usize, f: fn(u8) -> bool| {
+        while i < bytes.len() && f(bytes[i]) {
+            i += 1;
+        }
+        i
+    };
+    while i < bytes.len() {
+        let start = i;
+        let c = bytes[i];
+        if c.is_ascii_whitespace() {
+            i += 1;
+            continue;
+        }
+        if c.is_ascii_alphabetic() || c == b'_' {
+            i = take_while(i, |c| c.is_ascii_alphanumeric() || c == b'_');
+            tokens.push((start, Token::Ident(source[start..i].to_owned())));
+        } else if c.is_ascii_digit() {
+            i = take_while(i, |c| c.is_ascii_digit() || c == b'_');
+            let mut float = false;
+            // `1.` is a float but `1..` and `1.x` are not.
+            if bytes.get(i) == Some(&b'.')
+                && !matches!(bytes.get(i + 1), Some(c) if *c == b'.' || c.is_ascii_alphabetic() || *c == b'_')
+            {
+                float = true;
+                i = take_while(i + 1, |c| c.is_ascii_digit() || c == b'_');
+            }
+            if matches!(bytes.get(i), Some(b'e' | b'E')) {
+                float = true;
+                i += 1;
+                if matches!(bytes.get(i), Some(b'+' | b'-')) {
+                    i += 1;
+                }
+                i = take_while(i, |c| c.is_ascii_digit());
+            }
+            let number = source[start..i].replace('_', "");
+            // Ignore type suffixes like `1.0f32`.
+            let suffix = take_while(i, |c| c.is_ascii_alphanumeric() || c == b'_');
+            let float = float || source[i..suffix].starts_with('f');
+            i = suffix;
+            tokens.push((
+                start,
+                if float {
+                    Token::Float(number)
+                } else {
+                    Token::Int(number)
+                },
+            ));
+        } else if c == b'"' || c == b'\'' {
+            let mut value = String::new();
+            let mut chars = source[i + 1..].char_indices();
+            loop {
+                let Some((j, ch)) = chars.next() else {
+                    return fail(start, "Unterminated literal.");
+                };
+                match ch {
+                    '\\' => {
+                        let Some((_, escaped)) = chars.next() else {
+                            return fail(start, "Unterminated literal.");
+                        };
+                        value.push(match escaped {
+                            'n' => '\n',
+                            'r' => '\r',
+                            't' => '\t',
+                            '0' => '\0',
+                            '\\' | '"' | '\'' => escaped,
+                            _ => return fail(i + 1 + j, format!("Unknown escape `\\{escaped}`.")),
+                        });
+                    }
+                    ch if ch == c as char => {
+                        i += j + 2;
+                        break;
+                    }
+                    ch => value.push(ch),
+                }
+            }
+            if c == b'"' {
+                tokens.push((start, Token::Str(value)));
+            } else {
+                let mut chars = value.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(ch), None) => tokens.push((start, Token::Char(ch))),
+                    _ => return fail(start, "Invalid character literal."),
+                }
+            }
+        } else if let Some(punct) = PUNCTS.iter().find(|p| source[i..].starts_with(**p)) {
+            i += punct.len();
+            tokens.push((start, Token::Punct(punct)));
+        } else {
+            let ch = source[i..].chars().next().unwrap_or_default();
+            return fail(start, format!("Unexpected character `{ch}`."));
+        }
+    }
+    Ok(tokens)
+}
+
+/// A type path like `Handle<Image>` or a variant path like `Kind::Boss`.
+#[derive(Debug, Clone)]
+struct Path {
+    segments: Vec<String>,
+}
+
+impl Path {
+    fn name(&self) -> &str {
+        let last = self.segments.last().map(|x| x.as_str()).unwrap_or_default();
+        last.split('<').next().unwrap_or_default()
+    }
+
+    fn parent(&self) -> Option<Path> {
+        (self.segments.len() > 1).then(|| Path {
+            segments: self.segments[..self.segments.len() - 1].to_vec(),
+        })
+    }
+}
+
+impl Display for Path {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.segments.join("::"))
+    }
+}
+
+#[derive(Debug)]
+struct Expr {
+    offset: usize,
+    kind: ExprKind,
+}
+
+#[derive(Debug)]
+enum ExprKind {
+    Struct(Path, Vec<(usize, String, Expr)>),
+    Call(Path, Vec<Expr>),
+    Path(Path),
+    Effect(String, Box<Expr>),
+    Array(Vec<Expr>),
+    Tuple(Vec<Expr>),
+    Int(String),
+    Float(String),
+    Str(String),
+    Char(char),
+    Bool(bool),
+}
+
+impl ExprKind {
+    fn describe(&self) -> String {
+        match self {
+            ExprKind::Struct(path, _) | ExprKind::Call(path, _) | ExprKind::Path(path) => {
+                format!("`{path}`")
+            }
+            ExprKind::Effect(name, _) => format!("`@{name}`"),
+            ExprKind::Array(_) => "an array".to_owned(),
+            ExprKind::Tuple(_) => "a tuple".to_owned(),
+            ExprKind::Int(_) => "an integer".to_owned(),
+            ExprKind::Float(_) => "a float".to_owned(),
+            ExprKind::Str(_) => "a string".to_owned(),
+            ExprKind::Char(_) => "a character".to_owned(),
+            ExprKind::Bool(_) => "a bool".to_owned(),
+        }
+    }
+}
+
+/// Item of an entity and its children.
+#[derive(Debug)]
+struct SpawnAst {
+    item: Expr,
+    children: Vec<SpawnAst>,
+}
+
+struct Parser<'a> {
+    tokens: &'a [(usize, Token)],
+    pos: usize,
+    end: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(_, t)| t)
+    }
+
+    fn offset(&self) -> usize {
+        self.tokens
+            .get(self.pos)
+            .map(|(o, _)| *o)
+            .unwrap_or(self.end)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.peek().cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn is(&self, punct: &str) -> bool {
+        matches!(self.peek(), Some(Token::Punct(p)) if *p == punct)
+    }
+
+    fn eat(&mut self, punct: &str) -> bool {
+        let result = self.is(punct);
+        if result {
+            self.pos += 1;
+        }
+        result
+    }
+
+    fn unexpected<T>(&self, expected: &str) -> Result<T> {
+        match self.peek() {
+            Some(token) => fail(
+                self.offset(),
+                format!("Expected {expected}, found {token}."),
+            ),
+            None => fail(
+                self.offset(),
+                format!("Expected {expected}, found end of input."),
+            ),
+        }
+    }
+
+    fn expect(&mut self, punct: &str) -> Result<()> {
+        if self.eat(punct) {
+            Ok(())
+        } else {
+            self.unexpected(&format!("`{punct}`"))
+        }
+    }
+
+    fn ident(&mut self) -> Result<String> {
+        match self.peek() {
+            Some(Token::Ident(ident)) => {
+                let ident = ident.clone();
+                self.pos += 1;
+                Ok(ident)
+            }
+            _ => self.unexpected("an identifier"),
+        }
+    }
+
+    fn parse(mut self) -> Result<SpawnAst> {
+        let ast = self.parse_node()?;
+        if self.peek().is_some() {
+            if ast.children.is_empty() {
+                return self.unexpected("`=>` or end of input");
+            }
+            return self.unexpected("end of input");
+        }
+        Ok(ast)
+    }
+
+    /// Parse an item and its children, the item can be a tuple of components and bundles.
+    fn parse_node(&mut self) -> Result<SpawnAst> {
+        let item = self.parse_expr()?;
+        let children = self.parse_children()?;
+        Ok(SpawnAst { item, children })
+    }
+
+    fn parse_children(&mut self) -> Result<Vec<SpawnAst>> {
+        let mut children = Vec::new();
+        if !self.eat("=>") {
+            return Ok(children);
+        }
+        self.expect("[")?;
+        while !self.eat("]") {
+            children.push(self.parse_node()?);
+            if !self.eat(",") {
+                self.expect("]")?;
+                break;
+            }
+        }
+        Ok(children)
+    }
+
+    fn parse_list(&mut self, close: &str) -> Result<(Vec<Expr>, bool)> {
+        let mut items = Vec::new();
+        let mut trailing = false;
+        while !self.eat(close) {
+            items.push(self.parse_expr()?);
+            trailing = self.eat(",");
+            if !trailing {
+                self.expect(close)?;
+                break;
+            }
+        }
+        Ok((items, trailing))
+    }
+
+    fn parse_expr(&mut self) -> Result<Expr> {
+        let offset = self.offset();
+        let kind = match self.next() {
+            Some(Token::Punct("@")) => {
+                let name = self.ident()?;
+                if name == "load_with" {
+                    return fail(
+                        offset,
+                        "`@load_with` takes a closure and is not supported, use `@load`.",
+                    );
+                }
+                ExprKind::Effect(name, Box::new(self.parse_expr()?))
+            }
+            Some(Token::Punct("-")) => match self.next() {
+                Some(Token::Int(x)) => ExprKind::Int(format!("-{x}")),
+                Some(Token::Float(x)) => ExprKind::Float(format!("-{x}")),
+                _ => {
+                    self.pos -= 1;
+                    return self.unexpected("a number");
+                }
+            },
+            Some(Token::Int(x)) => ExprKind::Int(x),
+            Some(Token::Float(x)) => ExprKind::Float(x),
+            Some(Token::Str(x)) => ExprKind::Str(x),
+            Some(Token::Char(x)) => ExprKind::Char(x),
+            Some(Token::Punct("[")) => ExprKind::Array(self.parse_list("]")?.0),
+            Some(Token::Punct("(")) => match self.parse_list(")")? {
+                (mut items, false) if items.len() == 1 => return Ok(items.remove(0)),
+                (items, _) => ExprKind::Tuple(items),
+            },
+            Some(Token::Ident(ident))
+                if (ident == "true" || ident == "false") && !self.is("::") =>
+            {
+                ExprKind::Bool(ident == "true")
+            }
+            Some(Token::Ident(ident)) => {
+                let path = self.parse_path(ident)?;
+                if self.eat("{") {
+                    let mut fields = Vec::new();
+                    while !self.eat("}") {
+                        let offset = self.offset();
+                        let name = self.ident()?;
+                        self.expect(":")?;
+                        fields.push((offset, name, self.parse_expr()?));
+                        if !self.eat(",") {
+                            self.expect("}")?;
+                            break;
+                        }
+                    }
+                    ExprKind::Struct(path, fields)
+                } else if self.eat("(") {
+                    ExprKind::Call(path, self.parse_list(")")?.0)
+                } else {
+                    ExprKind::Path(path)
+                }
+            }
+            _ => {
+                self.pos -= 1;
+                return self.unexpected("an expression");
+            }
+        };
+        Ok(Expr { offset, kind })
+    }
+
+    fn parse_path(&mut self, first: String) -> Result<Path> {
+        let mut segments = vec![first];
+        loop {
+            if self.is("<") {
+                let generics = self.parse_generics()?;
+                if let Some(last) = segments.last_mut() {
+                    last.push_str(&generics);
+                }
+            } else if self.eat("::") {
+                if !self.is("<") {
+                    segments.push(self.ident()?);
+                }
+            } else {
+                return Ok(Path { segments });
+            }
+        }
+    }
+
+    fn parse_generics(&mut self) -> Result<String> {
+        self.expect("<")?;
+        let mut args = Vec::new();
+        while !self.eat(">") {
+            args.push(self.parse_type()?);
+            if !self.eat(",") {
+                self.expect(">")?;
+                break;
+            }
+        }
+        Ok(format!("<{}>", args.join(", ")))
+    }
+
+    fn parse_type(&mut self) -> Result<String> {
+        if self.eat("(") {
+            let mut types = Vec::new();
+            while !self.eat(")") {
+                types.push(self.parse_type()?);
+                if !self.eat(",") {
+                    self.expect(")")?;
+                    break;
+                }
+            }
+            Ok(format!("({})", types.join(", ")))
+        } else {
+            let ident = self.ident()?;
+            Ok(self.parse_path(ident)?.to_string())
+        }
+    }
+}
+
+struct Builder<'a> {
+    world: &'a mut World,
+    registry: &'a TypeRegistry,
+    /// If false, effects produce default placeholders instead of loading or adding assets.
+    effects: bool,
+}
+
+fn type_path(registration: &TypeRegistration) -> &'static str {
+    registration.type_info().type_path()
+}
+
+fn is_option(info: &EnumInfo) -> bool {
+    info.type_path().starts_with("core::option::Option<")
+}
+
+impl<'a> Builder<'a> {
+    fn resolve(&self, path: &Path, offset: usize) -> Result<&'a TypeRegistration> {
+        let name = path.to_string();
+        match self
+            .registry
+            .get_with_type_path(&name)
+            .or_else(|| self.registry.get_with_short_type_path(&name))
+        {
+            Some(registration) => Ok(registration),
+            None => fail(offset, format!("Type `{name}` is not registered.")),
+        }
+    }
+
+    fn get(&self, type_id: TypeId, name: &str, offset: usize) -> Result<&'a TypeRegistration> {
+        match self.registry.get(type_id) {
+            Some(registration) => Ok(registration),
+            None => fail(offset, format!("Type `{name}` is not registered.")),
+        }
+    }
+
+    fn build_node(&mut self, ast: &SpawnAst) -> Result<PrefabNode> {
+        let mut components = Vec::new();
+        self.build_items(&ast.item, &mut components)?;
+        let mut children = Vec::new();
+        for child in &ast.children {
+            children.push(self.build_node(child)?);
+        }
+        Ok(PrefabNode {
+            name: None,
+            components,
+            children,
+        })
+    }
+
+    /// Build a component or bundle, or the items of a tuple like `spawn!` does.
+    fn build_items(&mut self, item: &Expr, components: &mut Vec<Box<dyn Reflect>>) -> Result<()> {
+        let path = match &item.kind {
+            ExprKind::Tuple(items) => {
+                for item in items {
+                    self.build_items(item, components)?;
+                }
+                return Ok(());
+            }
+            ExprKind::Struct(path, _) | ExprKind::Call(path, _) | ExprKind::Path(path) => path,
+            _ => {
+                return fail(
+                    item.offset,
+                    format!(
+                        "Expected a component or bundle, found {}.",
+                        item.kind.describe()
+                    ),
+                )
+            }
+        };
+        // Enum variants like `Visibility::Hidden` resolve to their parent.
+        let registration = match (self.resolve(path, item.offset), path.parent()) {
+            (Err(err), Some(parent)) => self.resolve(&parent, item.offset).map_err(|_| err)?,
+            (registration, _) => registration?,
+        };
+        if registration.data::<ReflectComponent>().is_none()
+            && registration.data::<ReflectBundle>().is_none()
+        {
+            return fail(
+                item.offset,
+                format!(
+                    "Type `{}` is not a reflected component or bundle, \
+                    add `#[reflect(Component)]` or `#[reflect(Bundle)]`.",
+                    type_path(registration)
+                ),
+            );
+        }
+        components.push(self.build(item, registration)?);
+        Ok(())
+    }
+
+    fn mismatch<T>(&self, expr: &Expr, registration: &TypeRegistration) -> Result<T> {
+        fail(
+            expr.offset,
+            format!(
+                "Expected `{}`, found {}.",
+                type_path(registration),
+                expr.kind.describe()
+            ),
+        )
+    }
+
+    fn check_path(&self, path: &Path, expr: &Expr, registration: &TypeRegistration) -> Result<()> {
+        if self.resolve(path, expr.offset)?.type_id() != registration.type_id() {
+            return self.mismatch(expr, registration);
+        }
+        Ok(())
+    }
+
+    /// Convert a dynamic value to a concrete type if [`ReflectFromReflect`] is registered,
+    /// otherwise the dynamic value is converted when applied or inserted.
+    fn concrete(
+        &self,
+        value: Box<dyn Reflect>,
+        registration: &TypeRegistration,
+        offset: usize,
+    ) -> Result<Box<dyn Reflect>> {
+        let Some(reflect_from_reflect) = registration.data::<ReflectFromReflect>() else {
+            return Ok(value);
+        };
+        match reflect_from_reflect.from_reflect(value.as_ref()) {
+            Some(value) => Ok(value),
+            None => fail(
+                offset,
+                format!("Could not construct `{}`.", type_path(registration)),
+            ),
+        }
+    }
+
+    fn build(&mut self, expr: &Expr, registration: &TypeRegistration) -> Result<Box<dyn Reflect>> {
+        let info = registration.type_info();
+        // Values convert into `Some` like `Into<Option<T>>`.
+        if let TypeInfo::Enum(info) = info {
+            let is_variant = match &expr.kind {
+                ExprKind::Path(path) | ExprKind::Call(path, _) => {
+                    info.contains_variant(path.name())
+                }
+                ExprKind::Effect(name, _) => name == "some",
+                _ => false,
+            };
+            if is_option(info) && !is_variant {
+                return self.build_some(expr, expr, registration);
+            }
+        }
+        match (&expr.kind, info) {
+            (ExprKind::Effect(name, inner), _) => {
+                self.build_effect(name, expr, inner, registration)
+            }
+            (ExprKind::Struct(path, fields), TypeInfo::Struct(_)) => {
+                self.check_path(path, expr, registration)?;
+                let mut values = Vec::new();
+                for (offset, name, value) in fields {
+                    let index = self.field_index(registration, name, *offset)?;
+                    values.push((index, self.build_field(registration, index, value)?));
+                }
+                self.assemble(registration, values, expr.offset)
+            }
+            (ExprKind::Call(path, args), TypeInfo::TupleStruct(_)) => {
+                self.check_path(path, expr, registration)?;
+                self.build_positional(registration, expr, args)
+            }
+            (ExprKind::Path(path), TypeInfo::Struct(_) | TypeInfo::TupleStruct(_)) => {
+                self.check_path(path, expr, registration)?;
+                self.assemble(registration, Vec::new(), expr.offset)
+            }
+            (
+                ExprKind::Struct(path, _) | ExprKind::Call(path, _) | ExprKind::Path(path),
+                TypeInfo::Enum(info),
+            ) => self.build_variant(expr, path, info, registration),
+            (
+                ExprKind::Array(items) | ExprKind::Tuple(items),
+                TypeInfo::Struct(_) | TypeInfo::TupleStruct(_),
+            ) => self.build_positional(registration, expr, items),
+            (ExprKind::Array(items) | ExprKind::Tuple(items), TypeInfo::Tuple(info)) => {
+                if items.len() != info.field_len() {
+                    return self.mismatch(expr, registration);
+                }
+                let mut tuple = DynamicTuple::default();
+                for (item, field) in items.iter().zip(info.iter()) {
+                    let field = self.get(field.type_id(), field.type_path(), item.offset)?;
+                    tuple.insert_boxed(self.build(item, field)?);
+                }
+                tuple.set_represented_type(Some(registration.type_info()));
+                self.concrete(Box::new(tuple), registration, expr.offset)
+            }
+            (ExprKind::Array(items), TypeInfo::List(info)) => {
+                let item_type = self.get(
+                    info.item_type_id(),
+                    info.item_type_path_table().path(),
+                    expr.offset,
+                )?;
+                let mut list = DynamicList::default();
+                for item in items {
+                    list.push_box(self.build(item, item_type)?);
+                }
+                list.set_represented_type(Some(registration.type_info()));
+                self.concrete(Box::new(list), registration, expr.offset)
+            }
+            (ExprKind::Array(items), TypeInfo::Array(info)) => {
+                if items.len() != info.capacity() {
+                    return fail(
+                        expr.offset,
+                        format!(
+                            "Expected {} items for `{}`, found {}.",
+                            info.capacity(),
+                            info.type_path(),
+                            items.len()
+                        ),
+                    );
+                }
+                let item_type = self.get(
+                    info.item_type_id(),
+                    info.item_type_path_table().path(),
+                    expr.offset,
+                )?;
+                let mut values = Vec::new();
+                for item in items {
+                    values.push(self.build(item, item_type)?);
+                }
+                let mut array = DynamicArray::new(values.into_boxed_slice());
+                array.set_represented_type(Some(registration.type_info()));
+                self.concrete(Box::new(array), registration, expr.offset)
+            }
+            _ => self.build_primitive(expr, registration),
+        }
+    }
+
+    fn field_index(
+        &self,
+        registration: &TypeRegistration,
+        name: &str,
+        offset: usize,
+    ) -> Result<usize> {
+        let TypeInfo::Struct(info) = registration.type_info() else {
+            return fail(
+                offset,
+                format!("No field `{name}` on `{}`.", type_path(registration)),
+            );
+        };
+        match info.index_of(name) {
+            Some(index) => Ok(index),
+            None => fail(
+                offset,
+                format!(
+                    "No field `{name}` on `{}`, expected one of {}.",
+                    info.type_path(),
+                    info.iter()
+                        .map(|x| format!("`{}`", x.name()))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            ),
+        }
+    }
+
+    fn build_field(
+        &mut self,
+        registration: &TypeRegistration,
+        index: usize,
+        expr: &Expr,
+    ) -> Result<Box<dyn Reflect>> {
+        let field = match registration.type_info() {
+            TypeInfo::Struct(info) => info.field_at(index).map(|x| (x.type_id(), x.type_path())),
+            TypeInfo::TupleStruct(info) => {
+                info.field_at(index).map(|x| (x.type_id(), x.type_path()))
+            }
+            _ => None,
+        };
+        let Some((type_id, name)) = field else {
+            return fail(
+                expr.offset,
+                format!("Too many fields for `{}`.", type_path(registration)),
+            );
+        };
+        let field = self.get(type_id, name, expr.offset)?;
+        self.build(expr, field)
+    }
+
+    fn build_positional(
+        &mut self,
+        registration: &TypeRegistration,
+        expr: &Expr,
+        items: &[Expr],
+    ) -> Result<Box<dyn Reflect>> {
+        let mut values = Vec::new();
+        for (index, item) in items.iter().enumerate() {
+            values.push((index, self.build_field(registration, index, item)?));
+        }
+        self.assemble(registration, values, expr.offset)
+    }
+
+    /// Construct a struct or tuple struct from field values by index,
+    /// missing fields are taken from [`ReflectDefault`].
+    fn assemble(
+        &self,
+        registration: &TypeRegistration,
+        values: Vec<(usize, Box<dyn Reflect>)>,
+        offset: usize,
+    ) -> Result<Box<dyn Reflect>> {
+        if let Some(default) = registration.data::<ReflectDefault>() {
+            let mut result = default.default();
+            for (index, value) in values {
+                let field = match result.reflect_mut() {
+                    ReflectMut::Struct(x) => x.field_at_mut(index),
+                    ReflectMut::TupleStruct(x) => x.field_mut(index),
+                    _ => None,
+                };
+                if let Some(field) = field {
+                    field.apply(value.as_ref());
+                }
+            }
+            return Ok(result);
+        }
+        let mut values: Vec<_> = values.into_iter().map(Some).collect();
+        let mut take = |index: usize, name: &str| match values
+            .iter_mut()
+            .find(|x| matches!(x, Some((i, _)) if *i == index))
+            .and_then(Option::take)
+        {
+            Some((_, value)) => Ok(value),
+            None => fail(
+                offset,
+                format!(
+                    "Missing field `{name}` of `{}`, which does not implement `Default`.",
+                    type_path(registration)
+                ),
+            ),
+        };
+        match registration.type_info() {
+            TypeInfo::Struct(info) => {
+                let mut result = DynamicStruct::default();
+                for (index, field) in info.iter().enumerate() {
+                    result.insert_boxed(field.name(), take(index, field.name())?);
+                }
+                result.set_represented_type(Some(registration.type_info()));
+                self.concrete(Box::new(result), registration, offset)
+            }
+            TypeInfo::TupleStruct(info) => {
+                let mut result = DynamicTupleStruct::default();
+                for index in 0..info.field_len() {
+                    result.insert_boxed(take(index, &index.to_string())?);
+                }
+                result.set_represented_type(Some(registration.type_info()));
+                self.concrete(Box::new(result), registration, offset)
+            }
+            _ => fail(
+                offset,
+                format!("Could not construct `{}`.", type_path(registration)),
+            ),
+        }
+    }
+
+    fn build_variant(
+        &mut self,
+        expr: &Expr,
+        path: &Path,
+        info: &EnumInfo,
+        registration: &TypeRegistration,
+    ) -> Result<Box<dyn Reflect>> {
+        let name = path.name();
+        match path.parent() {
+            Some(parent) => self.check_path(&parent, expr, registration)?,
+            None if is_option(info) => (),
+            None => {
+                return fail(
+                    expr.offset,
+                    format!(
+                        "Expected `{}::{name}`, variants must be qualified.",
+                        info.type_path_table().short_path()
+                    ),
+                )
+            }
+        }
+        let Some(variant) = info.variant(name) else {
+            return fail(
+                expr.offset,
+                format!(
+                    "No variant `{name}` on `{}`, expected one of {}.",
+                    info.type_path(),
+                    info.variant_names()
+                        .iter()
+                        .map(|x| format!("`{x}`"))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            );
+        };
+        let value = match (&expr.kind, variant) {
+            (ExprKind::Path(_), VariantInfo::Unit(_)) => DynamicVariant::Unit,
+            (ExprKind::Call(_, args), VariantInfo::Tuple(variant))
+                if args.len() == variant.field_len() =>
+            {
+                let mut tuple = DynamicTuple::default();
+                for (arg, field) in args.iter().zip(variant.iter()) {
+                    let field = self.get(field.type_id(), field.type_path(), arg.offset)?;
+                    tuple.insert_boxed(self.build(arg, field)?);
+                }
+                DynamicVariant::Tuple(tuple)
+            }
+            (ExprKind::Struct(_, fields), VariantInfo::Struct(variant)) => {
+                let mut result = DynamicStruct::default();
+                for field in variant.iter() {
+                    let Some((_, _, value)) = fields.iter().find(|(_, n, _)| n == field.name())
+                    else {
+                        return fail(
+                            expr.offset,
+                            format!("Missing field `{}` of variant `{name}`.", field.name()),
+                        );
+                    };
+                    let field_type = self.get(field.type_id(), field.type_path(), value.offset)?;
+                    result.insert_boxed(field.name(), self.build(value, field_type)?);
+                }
+                if let Some((offset, field, _)) =
+                    fields.iter().find(|(_, n, _)| variant.field(n).is_none())
+                {
+                    return fail(*offset, format!("No field `{field}` on variant `{name}`."));
+                }
+                DynamicVariant::Struct(result)
+            }
+            _ => {
+                return fail(
+                    expr.offset,
+                    format!(
+                        "Variant `{name}` of `{}` has different fields.",
+                        info.type_path()
+                    ),
+                )
+            }
+        };
+        let mut result = DynamicEnum::new(name, value);
+        result.set_represented_type(Some(registration.type_info()));
+        self.concrete(Box::new(result), registration, expr.offset)
+    }
+
+    fn build_some(
+        &mut self,
+        expr: &Expr,
+        inner: &Expr,
+        registration: &TypeRegistration,
+    ) -> Result<Box<dyn Reflect>> {
+        let field = match registration.type_info() {
+            TypeInfo::Enum(info) if is_option(info) => match info.variant("Some") {
+                Some(VariantInfo::Tuple(variant)) => variant.field_at(0),
+                _ => None,
+            },
+            _ => None,
+        };
+        let Some(field) = field else {
+            return fail(
+                expr.offset,
+                format!(
+                    "`@some` expects an `Option`, found `{}`.",
+                    type_path(registration)
+                ),
+            );
+        };
+        let field = self.get(field.type_id(), field.type_path(), inner.offset)?;
+        let mut tuple = DynamicTuple::default();
+        tuple.insert_boxed(self.build(inner, field)?);
+        let mut result = DynamicEnum::new("Some", DynamicVariant::Tuple(tuple));
+        result.set_represented_type(Some(registration.type_info()));
+        self.concrete(Box::new(result), registration, expr.offset)
+    }
+
+    fn build_effect(
+        &mut self,
+        name: &str,
+        expr: &Expr,
+        inner: &Expr,
+        registration: &TypeRegistration,
+    ) -> Result<Box<dyn Reflect>> {
+        match name {
+            "some" => self.build_some(expr, inner, registration),
+            "arr" => match &inner.kind {
+                ExprKind::Array(_) => self.build(inner, registration),
+                kind => fail(
+                    inner.offset,
+                    format!("`@arr` expects an array, found {}.", kind.describe()),
+                ),
+            },
+            "load" => {
+                let ExprKind::Str(path) = &inner.kind else {
+                    return fail(
+                        inner.offset,
+                        format!("`@load` expects a string, found {}.", inner.kind.describe()),
+                    );
+                };
+                let load = self.reflect_load(name, expr, registration)?;
+                let asset_server = self.asset_server(name, expr)?;
+                if !self.effects {
+                    return self.placeholder(registration, expr.offset);
+                }
+                Ok(load.load(asset_server, path.clone()))
+            }
+            "load_all" | "load_glob" => self.build_load_all(name, expr, inner, registration),
+            "asset" => {
+                let Some(handle) = registration.data::<ReflectHandle>() else {
+                    return fail(
+                        expr.offset,
+                        format!(
+                            "`@asset` expects a `Handle`, found `{}`.",
+                            type_path(registration)
+                        ),
+                    );
+                };
+                let asset = self.get(handle.asset_type_id(), "asset", expr.offset)?;
+                let Some(reflect_asset) = asset.data::<ReflectAsset>() else {
+                    return fail(
+                        expr.offset,
+                        format!(
+                            "`@asset` requires `ReflectAsset` on `{}`, register it with `register_asset_reflect`.",
+                            type_path(asset)
+                        ),
+                    );
+                };
+                let value = self.build(inner, asset)?;
+                if !self.effects {
+                    return self.placeholder(registration, expr.offset);
+                }
+                let handle = handle.typed(reflect_asset.add(self.world, value.as_ref()));
+                Ok(handle)
+            }
+            _ => fail(
+                expr.offset,
+                format!(
+                    "Unknown effect `@{name}`, expected `@some`, `@arr`, `@load`, \
+                    `@load_all`, `@load_glob` or `@asset`."
+                ),
+            ),
+        }
+    }
+
+    /// Build `@load_all [..]` or `@load_glob ".."` into a list of handles.
+    fn build_load_all(
+        &mut self,
+        name: &str,
+        expr: &Expr,
+        inner: &Expr,
+        registration: &TypeRegistration,
+    ) -> Result<Box<dyn Reflect>> {
+        let TypeInfo::List(info) = registration.type_info() else {
+            return fail(
+                expr.offset,
+                format!(
+                    "`@{name}` expects a `Vec` of handles, found `{}`.",
+                    type_path(registration)
+                ),
+            );
+        };
+        let item_type = self.get(
+            info.item_type_id(),
+            info.item_type_path_table().path(),
+            expr.offset,
+        )?;
+        let load = self.reflect_load(name, expr, item_type)?;
+        let asset_server = self.asset_server(name, expr)?;
+        let paths = match (name, &inner.kind) {
+            ("load_all", ExprKind::Array(items)) => items
+                .iter()
+                .map(|item| match &item.kind {
+                    ExprKind::Str(path) => Ok(path.clone()),
+                    kind => fail(
+                        item.offset,
+                        format!("`@load_all` expects a string, found {}.", kind.describe()),
+                    ),
+                })
+                .collect::<Result<Vec<_>>>()?,
+            ("load_glob", ExprKind::Str(pattern)) if self.effects => {
+                glob_asset_paths(asset_server, pattern)
+                    .into_iter()
+                    .map(|path| path.to_string())
+                    .collect()
+            }
+            ("load_glob", ExprKind::Str(_)) => Vec::new(),
+            ("load_all", kind) => {
+                return fail(
+                    inner.offset,
+                    format!("`@load_all` expects an array, found {}.", kind.describe()),
+                )
+            }
+            (_, kind) => {
+                return fail(
+                    inner.offset,
+                    format!("`@load_glob` expects a string, found {}.", kind.describe()),
+                )
+            }
+        };
+        let mut list = DynamicList::default();
+        for path in paths {
+            list.push_box(if self.effects {
+                load.load(asset_server, path)
+            } else {
+                self.placeholder(item_type, expr.offset)?
+            });
+        }
+        list.set_represented_type(Some(registration.type_info()));
+        self.concrete(Box::new(list), registration, expr.offset)
+    }
+
+    fn reflect_load<'r>(
+        &self,
+        name: &str,
+        expr: &Expr,
+        registration: &'r TypeRegistration,
+    ) -> Result<&'r ReflectLoad> {
+        match registration.data::<ReflectLoad>() {
+            Some(load) => Ok(load),
+            None => fail(
+                expr.offset,
+                format!(
+                    "`@{name}` requires `ReflectLoad` on `{}`, register it with `ReflectLoadPlugin`.",
+                    type_path(registration)
+                ),
+            ),
+        }
+    }
+
+    fn asset_server(&self, name: &str, expr: &Expr) -> Result<&AssetServer> {
+        match self.world.get_resource::<AssetServer>() {
+            Some(asset_server) => Ok(asset_server),
+            None => fail(expr.offset, format!("`@{name}` requires an `AssetServer`.")),
+        }
+    }
+
+    /// Default value of a handle, used in place of effects while type checking.
+    fn placeholder(
+        &self,
+        registration: &TypeRegistration,
+        offset: usize,
+    ) -> Result<Box<dyn Reflect>> {
+        match registration.data::<ReflectDefault>() {
+            Some(default) => Ok(default.default()),
+            None => fail(
+                offset,
+                format!("Could not construct `{}`.", type_path(registration)),
+            ),
+        }
+    }
+
+    fn build_primitive(
+        &self,
+        expr: &Expr,
+        registration: &TypeRegistration,
+    ) -> Result<Box<dyn Reflect>> {
+        let type_id = registration.type_id();
+        macro_rules! parse {
+            ($s: expr, $($ty: ty),*) => {
+                { $(if type_id == TypeId::of::<$ty>() {
+                    return match $s.parse::<$ty>() {
+                        Ok(value) => Ok(Box::new(value)),
+                        Err(err) => fail(
+                            expr.offset,
+                            format!("Invalid `{}`: {err}.", stringify!($ty))
+                        ),
+                    };
+                })* }
+            };
+        }
+        match &expr.kind {
+            ExprKind::Int(x) => {
+                parse!(x, u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64)
+            }
+            ExprKind::Float(x) => parse!(x, f32, f64),
+            ExprKind::Str(x) if type_id == TypeId::of::<String>() => {
+                return Ok(Box::new(x.clone()))
+            }
+            ExprKind::Str(x) if type_id == TypeId::of::<Cow<'static, str>>() => {
+                return Ok(Box::new(Cow::<'static, str>::Owned(x.clone())))
+            }
+            ExprKind::Char(x) if type_id == TypeId::of::<char>() => return Ok(Box::new(*x)),
+            ExprKind::Bool(x) if type_id == TypeId::of::<bool>() => return Ok(Box::new(*x)),
+            _ => (),
+        }
+        self.mismatch(expr, registration)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy::{ecs::reflect::AppTypeRegistry, math::Vec2, render::texture::Image};
+    use bevy_asset::{AssetApp, Assets};
+    use bevy_ecs::{entity::Entity, world::World};
+    use bevy_hierarchy::Children;
+
+    use crate::{spawn, spawner_scope, test::*, ReflectLoadPlugin};
+
+    #[test]
+    fn spawn_from_str() {
+        let mut app = test_app();
+        app.add_plugins(ReflectLoadPlugin::<Image>::default());
+        app.add_plugins(ReflectLoadPlugin::<Value>::default());
+        app.register_type::<Stats>();
+        app.register_type::<Hitpoints>();
+        app.register_type::<StatsBundle>();
+        app.register_asset_reflect::<Value>();
+        app.register_type::<ValueHandle>();
+        let world = app.world_mut();
+        let entity = crate::spawn_from_str(
+            world,
+            r#"
+            (
+                Stats {
+                    speed: 2,
+                    size: @some [64., 64.],
+                    kind: Kind::Boss { level: 3 },
+                    tags: ["a", "b"],
+                    image: @load "circle.png",
+                    frames: @load_all ["one.value"],
+                },
+                Hitpoints(5),
+                ValueHandle(@asset Value(4)),
+            ) => [
+                Hitpoints(1_0),
+                Stats { size: [1, -2.5e0], kind: Kind::Named("x"), frames: @load_glob "frames/*.value" } => [
+                    bevy_spawn_fn::test::Hitpoints(3)
+                ],
+                StatsBundle {
+                    stats: Stats { size: @some [64., 64.], image: @load "circle.png" },
+                    hitpoints: Hitpoints(3),
+                },
+            ]
+        "#,
+        )
+        .unwrap();
+        let stats = world.entity(entity).get::<Stats>().unwrap();
+        assert_eq!(stats.speed, 2.0);
+        assert_eq!(stats.size, Some(Vec2::new(64., 64.)));
+        assert_eq!(stats.kind, Kind::Boss { level: 3 });
+        assert_eq!(stats.tags, vec!["a".to_owned(), "b".to_owned()]);
+        assert_eq!(stats.image.path().unwrap().to_string(), "circle.png");
+        assert_eq!(stats.frames[0].path().unwrap().to_string(), "one.value");
+        assert_eq!(world.entity(entity).get::<Hitpoints>(), Some(&Hitpoints(5)));
+        let handle = &world.entity(entity).get::<ValueHandle>().unwrap().0;
+        assert_eq!(world.resource::<Assets<Value>>().get(handle).unwrap().0, 4);
+        assert_eq!(world.resource::<Assets<Value>>().len(), 1);
+        let children = assert_children(world, entity, 3);
+        assert_eq!(
+            world.entity(children[0]).get::<Hitpoints>(),
+            Some(&Hitpoints(10))
+        );
+        let stats = world.entity(children[1]).get::<Stats>().unwrap();
+        assert_eq!(stats.speed, 0.0);
+        assert_eq!(stats.size, Some(Vec2::new(1., -2.5)));
+        assert_eq!(stats.kind, Kind::Named("x".to_owned()));
+        assert_eq!(stats.frames.len(), 2);
+        assert_children(world, children[1], 1);
+        let stats = world.entity(children[2]).get::<Stats>().unwrap();
+        assert_eq!(stats.size, Some(Vec2::new(64., 64.)));
+        assert!(stats.image.path().is_some());
+        assert_eq!(
+            world.entity(children[2]).get::<Hitpoints>(),
+            Some(&Hitpoints(3))
+        );
+
+        let mut error = |source: &str| {
+            let entities = world.entities().len();
+            let err = crate::spawn_from_str(world, source).unwrap_err();
+            assert_eq!(world.entities().len(), entities);
+            err.to_string()
+        };
+        assert_eq!(
+            error("Stats {\n    sped: 1 }"),
+            "2:5: No field `sped` on `bevy_spawn_fn::test::Stats`, \
+            expected one of `speed`, `size`, `kind`, `tags`, `image`, `frames`."
+        );
+        assert_eq!(
+            error("Hitpoints(\"x\")"),
+            "1:11: Expected `u32`, found a string."
+        );
+        assert_eq!(error("Missing"), "1:1: Type `Missing` is not registered.");
+        assert_eq!(
+            error("Stats { kind: Kind::Big }"),
+            "1:15: No variant `Big` on `bevy_spawn_fn::test::Kind`, \
+            expected one of `Normal`, `Boss`, `Named`."
+        );
+        assert_eq!(
+            error("Stats { speed: @boxed 1 }"),
+            "1:16: Unknown effect `@boxed`, expected `@some`, `@arr`, `@load`, \
+            `@load_all`, `@load_glob` or `@asset`."
+        );
+        assert_eq!(
+            error("Stats { kind: Named(\"x\") }"),
+            "1:15: Expected `Kind::Named`, variants must be qualified."
+        );
+        assert_eq!(
+            error("Stats { image: @load_with \"circle.png\", |s| () }"),
+            "1:16: `@load_with` takes a closure and is not supported, use `@load`."
+        );
+        // Effects are not performed if type checking fails.
+        assert_eq!(
+            error("(ValueHandle(@asset Value(1)), Hitpoints(\"x\"))"),
+            "1:42: Expected `u32`, found a string."
+        );
+        // Like `spawn!`, multiple items must be a tuple.
+        assert_eq!(
+            error("Hitpoints(1), Hitpoints(2)"),
+            "1:13: Expected `=>` or end of input, found `,`."
+        );
+        assert_eq!(
+            error("Hitpoints(1) => [A, B, C] => [A]"),
+            "1:27: Expected end of input, found `=>`."
+        );
+        assert_eq!(
+            error("Stats {"),
+            "1:8: Expected an identifier, found end of input."
+        );
+        assert_eq!(
+            error("Kind::Normal"),
+            "1:1: Type `bevy_spawn_fn::test::Kind` is not a reflected component or bundle, \
+            add `#[reflect(Component)]` or `#[reflect(Bundle)]`."
+        );
+        assert_eq!(
+            error("Stats::Normal"),
+            "1:1: Type `Stats::Normal` is not registered."
+        );
+        assert_eq!(world.resource::<Assets<Value>>().len(), 1);
+    }
+
+    /// Component names of an entity and its descendants, with [`Hitpoints`] values.
+    fn describe_tree(world: &World, entity: Entity) -> String {
+        let mut names: Vec<_> = world
+            .inspect_entity(entity)
+            .into_iter()
+            .map(|info| info.name().to_owned())
+            .filter(|name| !name.ends_with("Parent") && !name.ends_with("Children"))
+            .collect();
+        names.sort();
+        let hitpoints = world.entity(entity).get::<Hitpoints>();
+        let children = world.entity(entity).get::<Children>().into_iter().flatten();
+        let children: Vec<_> = children.map(|&x| describe_tree(world, x)).collect();
+        format!("{names:?} {hitpoints:?} [{}]", children.join(", "))
+    }
+
+    #[test]
+    fn spawn_from_str_parity() {
+        let mut world = World::new();
+        world.init_resource::<AppTypeRegistry>();
+        {
+            let registry = world.resource::<AppTypeRegistry>();
+            let mut registry = registry.write();
+            registry.register::<A>();
+            registry.register::<B>();
+            registry.register::<C>();
+            registry.register::<Hitpoints>();
+        }
+        macro_rules! parity {
+            ($($tt: tt)*) => {{
+                let source = stringify!($($tt)*);
+                let from_str = crate::spawn_from_str(&mut world, source).unwrap();
+                let from_macro = spawner_scope(&mut world, || spawn!($($tt)*));
+                assert_eq!(
+                    describe_tree(&world, from_str),
+                    describe_tree(&world, from_macro),
+                    "{source}"
+                );
+            }};
+        }
+        parity!(A);
+        parity!((A, Hitpoints(2)));
+        parity!((A, (B, C)));
+        parity!(A => [B, (B, C), (A, Hitpoints(1)) => [C]]);
+        parity!((A, Hitpoints(4)) => [(B, C) => [(A, B) => [C,]],]);
+    }
+}
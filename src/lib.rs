@@ -1,8 +1,7 @@
 #![doc = include_str!("../README.md")]
 #![allow(clippy::type_complexity)]
-use bevy_asset::{meta::Settings, Asset, AssetPath, Assets, Handle, UntypedHandle};
+use bevy_asset::{meta::Settings, Asset, AssetPath, Assets, Handle, LoadedFolder, UntypedHandle};
 use bevy_ecs::{
-    bundle::Bundle,
     component::{Component, ComponentHooks, StorageType},
     entity::Entity,
     system::EntityCommands,
@@ -11,35 +10,219 @@ use bevy_ecs::{
 use bevy_hierarchy::{BuildChildren, BuildWorldChildren};
 pub use default_constructor::InferInto;
 use scoped_tls_hkt::scoped_thread_local;
-use std::{borrow::Cow, cell::Cell, marker::PhantomData, mem, ptr::null_mut};
+use std::{
+    borrow::Cow, cell::Cell, fmt::Display, marker::PhantomData, mem, panic::Location, ptr::null_mut,
+};
 
+mod glob;
+mod handle_slot;
+mod interpreter;
+mod load_on_insert;
+mod prefab;
+mod preload;
 mod spawnable;
+mod tracking;
+pub use handle_slot::*;
+pub use interpreter::*;
+pub use load_on_insert::*;
+pub use prefab::*;
+pub use preload::*;
 pub use spawnable::*;
+pub use tracking::*;
 
 #[doc(hidden)]
 pub use bevy_asset::AssetServer;
 #[doc(hidden)]
+pub use bevy_ecs::bundle::Bundle;
+#[doc(hidden)]
 pub use bevy_ecs::system::{Commands, Res};
+#[doc(hidden)]
+pub use bevy_ecs::world::World;
 pub use bevy_spawn_fn_derive::*;
 #[doc(hidden)]
 pub use default_constructor;
 
 /// Convert an item to a handle by registering using [`AssetServer::add`].
+///
+/// If not in an [`asset_server_scope`], see [`AssetEffect::from_deferred_asset`].
 #[doc(hidden)]
-pub fn asset<T: Asset>(a: T) -> Handle<T> {
-    ASSET_SERVER.with(|s| s.add(a))
+#[track_caller]
+pub fn asset<T: AssetEffect>(a: T::Asset) -> T {
+    if ASSET_SERVER.is_set() {
+        T::from_handle(ASSET_SERVER.with(|s| s.add(a)))
+    } else {
+        T::from_deferred_asset(a)
+    }
 }
 
 /// Convert a [`AssetPath`] to a handle by loading using [`AssetServer::load`].
+///
+/// If not in an [`asset_server_scope`], see [`AssetEffect::from_deferred_path`].
+#[doc(hidden)]
+#[track_caller]
+pub fn load<T: AssetEffect>(a: AssetPath<'static>) -> T {
+    if ASSET_SERVER.is_set() {
+        T::from_handle(ASSET_SERVER.with(|s| s.load(a)))
+    } else {
+        T::from_deferred_path(a)
+    }
+}
+
+/// Load a path with loader settings using [`AssetServer::load_with_settings`].
+///
+/// If not in an [`asset_server_scope`], see [`AssetEffect::from_deferred_path_with_settings`].
+#[track_caller]
+pub fn load_with_settings<T: AssetEffect, S: Settings>(
+    path: impl Into<AssetPath<'static>>,
+    settings: impl Fn(&mut S) + Send + Sync + 'static,
+) -> T {
+    if ASSET_SERVER.is_set() {
+        T::from_handle(ASSET_SERVER.with(|s| s.load_with_settings(path, settings)))
+    } else {
+        T::from_deferred_path_with_settings(path.into(), settings)
+    }
+}
+
+/// Input of the `@load_with` effect, created from `path, |settings: &mut S| ..`.
+pub struct LoadWith<S> {
+    path: AssetPath<'static>,
+    settings: Box<dyn Fn(&mut S) + Send + Sync>,
+}
+
+impl<P, S, F> From<(P, F)> for LoadWith<S>
+where
+    P: Into<AssetPath<'static>>,
+    F: Fn(&mut S) + Send + Sync + 'static,
+{
+    fn from((path, settings): (P, F)) -> Self {
+        LoadWith {
+            path: path.into(),
+            settings: Box::new(settings),
+        }
+    }
+}
+
+/// Load a path with loader settings, see [`load_with_settings`].
+#[doc(hidden)]
+#[track_caller]
+pub fn load_with<T: AssetEffect, S: Settings>(a: LoadWith<S>) -> T {
+    load_with_settings(a.path, a.settings)
+}
+
+/// Input of the `@load_all` effect, created from an iterator of paths.
+pub struct AssetPaths(pub Vec<AssetPath<'static>>);
+
+impl<I> From<I> for AssetPaths
+where
+    I: IntoIterator,
+    I::Item: Into<AssetPath<'static>>,
+{
+    fn from(value: I) -> Self {
+        AssetPaths(value.into_iter().map(Into::into).collect())
+    }
+}
+
+/// Load multiple paths using [`AssetServer::load`].
+///
+/// If not in an [`asset_server_scope`], see [`AssetCollection::from_deferred_paths`].
+#[doc(hidden)]
+#[track_caller]
+pub fn load_all<T: AssetCollection>(a: AssetPaths) -> T {
+    if ASSET_SERVER.is_set() {
+        ASSET_SERVER.with(|s| T::from_handles(a.0.into_iter().map(|x| s.load(x))))
+    } else {
+        T::from_deferred_paths(a.0)
+    }
+}
+
+/// Load all assets matching a glob pattern, supports `*`, `?` and `**`.
+///
+/// Directories are read by the asset reader of the pattern's asset source when called,
+/// and matching paths are loaded in alphabetical order.
+///
+/// If not in an [`asset_server_scope`], see [`AssetCollection::from_deferred_glob`].
+#[doc(hidden)]
+#[track_caller]
+pub fn load_glob<T: AssetCollection>(a: String) -> T {
+    if ASSET_SERVER.is_set() {
+        ASSET_SERVER
+            .with(|s| T::from_handles(glob::glob_asset_paths(s, &a).into_iter().map(|x| s.load(x))))
+    } else {
+        T::from_deferred_glob(a)
+    }
+}
+
+/// Load a folder using [`AssetServer::load_folder`].
+///
+/// If not in an [`asset_server_scope`], inserts [`LoadFolderMe`] after spawning instead.
 #[doc(hidden)]
-pub fn load<T: Asset>(a: AssetPath<'static>) -> Handle<T> {
-    ASSET_SERVER.with(|s| s.load(a))
+#[track_caller]
+pub fn load_folder(a: AssetPath<'static>) -> Handle<LoadedFolder> {
+    if ASSET_SERVER.is_set() {
+        ASSET_SERVER.with(|s| s.load_folder(a))
+    } else {
+        defer_insert(move |entity| entity.insert(LoadFolderMe::new(a.to_string())));
+        Handle::default()
+    }
+}
+
+/// Insert a component after the entity is spawned by [`spawn!`].
+///
+/// # Panics
+///
+/// If not called inside [`deferred_assets`], unless in [`try_spawn!`].
+#[track_caller]
+fn defer_insert(f: impl FnOnce(&mut EntityMutSpawner) + 'static) {
+    let mut deferred = DEFERRED.take();
+    match &mut deferred {
+        Some(items) => items.push(Box::new(f)),
+        None => no_asset_server(Location::caller()),
+    }
+    DEFERRED.set(deferred);
+}
+
+/// Report an asset effect that cannot be deferred without an [`AssetServer`].
+///
+/// # Panics
+///
+/// If not in [`try_spawn!`], otherwise the first location is returned as an error.
+pub(crate) fn no_asset_server(location: &'static Location<'static>) {
+    match MISSING_ASSET_SERVER.get() {
+        Some(None) => MISSING_ASSET_SERVER.set(Some(Some(location))),
+        Some(Some(_)) => (),
+        None => panic!("{}", SpawnError::NoAssetServer(location)),
+    }
+}
+
+/// Top of the spawner stack.
+///
+/// Previous items are stored on the call stack and restored by [`Reset`].
+#[derive(Clone, Copy)]
+struct SpawnerScope {
+    spawner: *mut Spawner<'static, 'static, 'static>,
+    /// If not null, entities spawned in this scope are collected here.
+    children: *mut Vec<Entity>,
+}
+
+impl SpawnerScope {
+    const EMPTY: Self = SpawnerScope {
+        spawner: null_mut(),
+        children: null_mut(),
+    };
 }
 
 // A reference to the spawner scope.
-thread_local! {static SPAWNER: Cell<*mut Spawner<'static, 'static, 'static>> = const { Cell::new(null_mut()) } }
+thread_local! {static SPAWNER: Cell<SpawnerScope> = const { Cell::new(SpawnerScope::EMPTY) } }
+// `Some` in `try_spawn!`, contains the location of the first asset effect that could not be deferred.
+thread_local! {static MISSING_ASSET_SERVER: Cell<Option<Option<&'static Location<'static>>>> = const { Cell::new(None) } }
 scoped_thread_local!(static ASSET_SERVER: AssetServer);
 
+/// A component insertion deferred until after spawning.
+type DeferredInsert = Box<dyn FnOnce(&mut EntityMutSpawner)>;
+
+// Insertions collected by `@asset` and `@load` outside of an asset server scope.
+thread_local! {static DEFERRED: Cell<Option<Vec<DeferredInsert>>> = const { Cell::new(None) } }
+
 /// Spawn a [`IntoSpawnable`] using a thread local spawner, returns [`Entity`].
 ///
 /// This can be manually created via [`spawner_scope`] or used inside an system or function annotated with
@@ -48,25 +231,254 @@ scoped_thread_local!(static ASSET_SERVER: AssetServer);
 /// # Syntax
 ///
 /// See [`infer_construct!`] and module level documentation of [`default_constructor`].
+///
+/// Children can be declared with `=> [..]` after the item, separated by commas.
+///
+/// ```ignore
+/// spawn!(NodeBundle { .. } => [
+///     ButtonBundle { .. } => [
+///         TextBundle { .. }
+///     ],
+///     ImageBundle { .. },
+/// ])
+/// ```
+///
+/// # Assets
+///
+/// `@asset` and `@load` use the current [`asset_server_scope`].
+/// `@load_with "path", |s: &mut S| ..` loads with loader settings,
+/// the closure ends at the next comma so use a block if it contains commas.
+///
+/// `@load_all [..]` and `@load_glob "frames/*.png"` load multiple paths into a `Vec` or [`Handles`],
+/// `@load_folder` loads a `Handle<LoadedFolder>`.
+///
+/// ```ignore
+/// spawn!(SpriteBundle {
+///     texture: @load_with "circle.png", |s: &mut ImageLoaderSettings| s.is_srgb = false,
+/// })
+/// ```
+///
+/// If not in an [`asset_server_scope`], a default [`Handle`] is used and
+/// [`AddMe`] or [`LoadMe`] is inserted after spawning, which replaces the handle component.
+/// This only works if the handle is a component, the inserted component panics
+/// if the handle is a field, use [`HandleSlot`] for fields instead.
+/// Items without these effects are spawned as is, so bundles stay [`Spawnable::IS_STATIC`].
 #[macro_export]
 macro_rules! spawn {
-    ($($tt: tt)*) => {
+    (@__munch [$($acc: tt)*] => [$($children: tt)*]) => {
+        $crate::spawn($crate::WithChildren::new(
+            $crate::spawn!(@__spawnable $($acc)*),
+            || {
+                $crate::__spawn_children!([$crate::spawn] [] [] [] $($children)*);
+            }
+        ))
+    };
+    (@__munch [$($acc: tt)*] $head: tt $($rest: tt)*) => {
+        $crate::spawn!(@__munch [$($acc)* $head] $($rest)*)
+    };
+    (@__munch [$($acc: tt)*]) => {
+        $crate::spawn($crate::spawn!(@__spawnable $($acc)*))
+    };
+    (@__spawnable $($tt: tt)*) => {
+        $crate::__if_asset_effects! {
+            [$($tt)*]
+            {
+                $crate::deferred_assets(|| {
+                    $crate::IntoSpawnable::into_spawnable($crate::spawn!(@__construct $($tt)*))
+                })
+            } else {
+                $crate::IntoSpawnable::into_spawnable($crate::spawn!(@__construct $($tt)*))
+            }
+        }
+    };
+    (@__construct $($tt: tt)*) => {
         {
             #[allow(unused)]
             use $crate::default_constructor::effects::*;
             #[allow(unused)]
-            use $crate::{asset, load};
-            $crate::spawn(
-                $crate::default_constructor::meta_default_constructor! {
-                    [$crate::default_constructor::infer_into]
-                    $($tt)*
-                }
-            )
+            use $crate::{asset, load, load_all, load_folder, load_glob, load_with};
+            $crate::__construct! {
+                [$crate::default_constructor::meta_default_constructor]
+                [$crate::default_constructor::infer_into]
+                $($tt)*
+            }
         }
     };
+    ($($tt: tt)*) => {
+        $crate::spawn!(@__munch [] $($tt)*)
+    };
+}
+
+/// Split the children of [`spawn!`] on top level commas and invoke `$mac!($($args)* child)` on each,
+/// commas in generic arguments like `Foo::<A, B>` are not split.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __spawn_children {
+    ([$($mac: tt)*] [$($args: tt)*] [$($acc: tt)+] [] , $($rest: tt)*) => {
+        $($mac)*!($($args)* $($acc)*);
+        $crate::__spawn_children!([$($mac)*] [$($args)*] [] [] $($rest)*);
+    };
+    ([$($mac: tt)*] [$($args: tt)*] [$($acc: tt)*] [$($depth: tt)*] < $($rest: tt)*) => {
+        $crate::__spawn_children!([$($mac)*] [$($args)*] [$($acc)* <] [$($depth)* <] $($rest)*);
+    };
+    ([$($mac: tt)*] [$($args: tt)*] [$($acc: tt)*] [< $($depth: tt)*] > $($rest: tt)*) => {
+        $crate::__spawn_children!([$($mac)*] [$($args)*] [$($acc)* >] [$($depth)*] $($rest)*);
+    };
+    ([$($mac: tt)*] [$($args: tt)*] [$($acc: tt)*] [< < $($depth: tt)*] >> $($rest: tt)*) => {
+        $crate::__spawn_children!([$($mac)*] [$($args)*] [$($acc)* >>] [$($depth)*] $($rest)*);
+    };
+    ([$($mac: tt)*] [$($args: tt)*] [$($acc: tt)*] [$($depth: tt)*] $head: tt $($rest: tt)*) => {
+        $crate::__spawn_children!([$($mac)*] [$($args)*] [$($acc)* $head] [$($depth)*] $($rest)*);
+    };
+    ([$($mac: tt)*] [$($args: tt)*] [$($acc: tt)+] [$($depth: tt)*]) => {
+        $($mac)*!($($args)* $($acc)*);
+    };
+    ([$($mac: tt)*] [$($args: tt)*] [] [$($depth: tt)*]) => {};
+}
+
+/// Spawn an iterator of [`IntoSpawnable`]s using a thread local spawner, returns [`Vec<Entity>`].
+///
+/// # Syntax
+///
+/// Either an expression that is an iterator of [`IntoSpawnable`]s, or
+/// `for pat in iter => ..` where `..` uses the same syntax as [`spawn!`].
+///
+/// ```ignore
+/// spawn_batch!(for pos in positions => SpriteBundle {
+///     transform: Transform {
+///         translation: pos,
+///     }
+/// })
+/// ```
+#[macro_export]
+macro_rules! spawn_batch {
+    (for $pat: pat in $iter: expr => $($tt: tt)*) => {
+        $crate::spawn_batch(::core::iter::IntoIterator::into_iter($iter).map(|$pat| {
+            $crate::spawn!(@__spawnable $($tt)*)
+        }))
+    };
+    ($iter: expr) => {
+        $crate::spawn_batch($iter)
+    };
+}
+
+/// Spawn a [`IntoSpawnable`] using a thread local spawner, returns [`Result<Entity, SpawnError>`].
+///
+/// Unlike [`spawn!`], this returns an error instead of panicking if called outside of a
+/// [`spawner_scope`], or if an asset effect cannot be deferred outside of an [`asset_server_scope`].
+/// The item is not spawned if an error occurred, but entities spawned by nested
+/// [`spawn!`] calls in its constructor are not despawned.
+///
+/// # Syntax
+///
+/// Same as [`spawn!`]. Children are spawned with [`try_spawn!`] after the item,
+/// the first error of a child is returned after the item is spawned.
+#[macro_export]
+macro_rules! try_spawn {
+    (@__munch [$($acc: tt)*] => [$($children: tt)*]) => {{
+        let mut result = ::core::result::Result::Ok(());
+        $crate::try_spawn_with(|| {
+            $crate::WithChildren::new($crate::spawn!(@__spawnable $($acc)*), || {
+                $crate::__spawn_children!([$crate::try_spawn] [@__child result] [] [] $($children)*);
+            })
+        })
+        .and_then(|entity| result.map(|_| entity))
+    }};
+    (@__munch [$($acc: tt)*] $head: tt $($rest: tt)*) => {
+        $crate::try_spawn!(@__munch [$($acc)* $head] $($rest)*)
+    };
+    (@__munch [$($acc: tt)*]) => {
+        $crate::try_spawn_with(|| $crate::spawn!(@__spawnable $($acc)*))
+    };
+    (@__child $result: ident $($tt: tt)*) => {
+        $result = $result.and($crate::try_spawn!($($tt)*).map(|_| ()));
+    };
+    ($($tt: tt)*) => {
+        $crate::try_spawn!(@__munch [] $($tt)*)
+    };
+}
+
+/// Error returned by [`try_spawn()`], [`try_spawn!`] and [`ReflectSpawnable::try_spawn`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpawnError {
+    /// Not called in a [`spawner_scope`].
+    NoSpawner(&'static Location<'static>),
+    /// An asset effect that cannot be deferred, like `@load_glob` into a [`Vec`],
+    /// not called in an [`asset_server_scope`].
+    NoAssetServer(&'static Location<'static>),
+    /// `@asset` or `@load` outside of an [`asset_server_scope`] created a [`Handle`]
+    /// or [`Handles`] that is not a component of the spawned entity.
+    NotAComponent(&'static Location<'static>),
+    /// [`ReflectSpawnable`] could not convert a value to its type via
+    /// [`FromReflect`](bevy_reflect::FromReflect).
+    FromReflect {
+        expected: &'static str,
+        found: Option<&'static str>,
+        location: &'static Location<'static>,
+    },
+}
+
+impl SpawnError {
+    /// Returns the location of the error.
+    pub fn location(&self) -> &'static Location<'static> {
+        match self {
+            SpawnError::NoSpawner(location) => location,
+            SpawnError::NoAssetServer(location) => location,
+            SpawnError::NotAComponent(location) => location,
+            SpawnError::FromReflect { location, .. } => location,
+        }
+    }
+}
+
+impl Display for SpawnError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SpawnError::NoSpawner(location) => {
+                write!(f, "Must be called in a spawner scope, at {location}.")
+            }
+            SpawnError::NoAssetServer(location) => {
+                write!(f, "Must be called in an asset server scope, at {location}.")
+            }
+            SpawnError::NotAComponent(location) => write!(
+                f,
+                "Outside of an asset server scope, handles must be components, use `HandleSlot` for fields, at {location}."
+            ),
+            SpawnError::FromReflect {
+                expected,
+                found: Some(found),
+                location,
+            } => write!(f, "Expected `{expected}`, found `{found}`, at {location}."),
+            SpawnError::FromReflect {
+                expected,
+                found: None,
+                location,
+            } => write!(
+                f,
+                "Expected `{expected}`, found a value without type info, at {location}."
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SpawnError {}
+
+struct Reset(SpawnerScope);
+
+struct ResetDeferred(Option<Vec<DeferredInsert>>);
+
+struct ResetMissingAssetServer(Option<Option<&'static Location<'static>>>);
+
+impl Drop for ResetMissingAssetServer {
+    fn drop(&mut self) {
+        MISSING_ASSET_SERVER.set(self.0);
+    }
 }
 
-struct Reset(*mut Spawner<'static, 'static, 'static>);
+impl Drop for ResetDeferred {
+    fn drop(&mut self) {
+        DEFERRED.set(self.0.take());
+    }
+}
 
 impl Drop for Reset {
     fn drop(&mut self) {
@@ -76,30 +488,140 @@ impl Drop for Reset {
 
 /// Push a [`Spawner`] onto thread local storage in a scope.
 pub fn spawner_scope<'a, 'b: 'a, 'c: 'a, T>(
-    spawner: &'a mut impl AsSpawner<'a, 'b, 'c>,
+    spawner: &'a mut (impl AsSpawner<'a, 'b, 'c> + ?Sized),
+    f: impl FnOnce() -> T,
+) -> T {
+    push_spawner(&mut spawner.as_spawner(), None, f)
+}
+
+/// Push a [`Spawner`] onto the thread local stack in a scope,
+/// if `children` is set, spawned entities are collected into it.
+pub(crate) fn push_spawner<T>(
+    spawner: &mut Spawner,
+    children: Option<&mut Vec<Entity>>,
     f: impl FnOnce() -> T,
 ) -> T {
-    let mut spawner = spawner.as_spawner();
-    let prev = SPAWNER.replace((&mut spawner as *mut Spawner).cast());
+    let prev = SPAWNER.replace(SpawnerScope {
+        spawner: (spawner as *mut Spawner).cast(),
+        children: children.map_or(null_mut(), |x| x as *mut _),
+    });
     // for panic safety, this will reset the spawner during unwinding.
     let _reset = Reset(prev);
     f()
 }
 
+/// Exclusively access the top of the thread local spawner stack,
+/// returns `None` if not in a [`spawner_scope`].
+///
+/// The stack is empty during `f`, unless new scopes are pushed.
+pub(crate) fn with_spawner<T>(
+    f: impl FnOnce(&mut Spawner, Option<&mut Vec<Entity>>) -> T,
+) -> Option<T> {
+    let scope = SPAWNER.replace(SpawnerScope::EMPTY);
+    // for panic safety, this will reset the spawner during unwinding.
+    let _reset = Reset(scope);
+    // Safety: `SPAWNER` is only set by `push_spawner` and
+    // exclusively accessed in `with_spawner`.
+    let spawner = unsafe { scope.spawner.as_mut() }?;
+    // Safety: same as above.
+    let children = unsafe { scope.children.as_mut() };
+    Some(f(spawner, children))
+}
+
+/// Access the [`World`] of the current [`spawner_scope`],
+/// returns `None` if not in a [`spawner_scope`] or the spawner is not a [`World`].
+///
+/// This is useful in exclusive systems annotated with [`spawner_system`],
+/// since the `World` is borrowed by the spawner.
+///
+/// [`spawn!`] cannot be used inside `f`.
+pub fn with_world<T>(f: impl FnOnce(&mut World) -> T) -> Option<T> {
+    with_spawner(|spawner, _| match spawner {
+        Spawner::World(world) => Some(f(world)),
+        _ => None,
+    })
+    .flatten()
+}
+
 /// Push a [`AssetServer`] onto thread local storage in a scope.
 pub fn asset_server_scope<T>(asset_server: &AssetServer, f: impl FnOnce() -> T) -> T {
     ASSET_SERVER.set(asset_server, f)
 }
 
+/// Push a [`AssetServer`] onto thread local storage in a scope if it exists.
+#[doc(hidden)]
+pub fn optional_asset_server_scope<T>(
+    asset_server: Option<&AssetServer>,
+    f: impl FnOnce() -> T,
+) -> T {
+    match asset_server {
+        Some(asset_server) => ASSET_SERVER.set(asset_server, f),
+        None => f(),
+    }
+}
+
 /// Spawn a [`IntoSpawnable`] using the current thread local [`spawner_scope`].
+///
+/// # Panics
+///
+/// If not called in a [`spawner_scope`].
+#[track_caller]
 pub fn spawn(spawned: impl IntoSpawnable) -> Entity {
-    let ptr = SPAWNER.replace(null_mut());
-    // for panic safety, this will reset the spawner during unwinding.
-    let __reset = Reset(ptr);
-    // Safety: `SPAWNER` is only set by `spawner_scope` and
-    // exclusively accessed in `spawn`.
-    let spawner = unsafe { ptr.as_mut().expect("Must be called in a spawner scope.") };
-    spawner.spawn(spawned)
+    match try_spawn(spawned) {
+        Ok(entity) => entity,
+        Err(err) => panic!("{err}"),
+    }
+}
+
+/// Spawn a [`IntoSpawnable`] using the current thread local [`spawner_scope`],
+/// returns an error if not called in a [`spawner_scope`].
+#[track_caller]
+pub fn try_spawn(spawned: impl IntoSpawnable) -> Result<Entity, SpawnError> {
+    let location = Location::caller();
+    with_spawner(|spawner, children| {
+        let entity = spawner.spawn(spawned);
+        if let Some(children) = children {
+            children.push(entity);
+        }
+        entity
+    })
+    .ok_or(SpawnError::NoSpawner(location))
+}
+
+/// Spawn an iterator of [`IntoSpawnable`]s using the current thread local [`spawner_scope`],
+/// see [`Spawner::spawn_batch`].
+///
+/// # Panics
+///
+/// If not called in a [`spawner_scope`].
+#[track_caller]
+pub fn spawn_batch<T: IntoSpawnable>(spawned: impl IntoIterator<Item = T>) -> Vec<Entity> {
+    with_spawner(|spawner, children| {
+        let entities = spawner.spawn_batch(spawned);
+        if let Some(children) = children {
+            children.extend(entities.iter().copied());
+        }
+        entities
+    })
+    .unwrap_or_else(|| panic!("{}", SpawnError::NoSpawner(Location::caller())))
+}
+
+/// Construct a [`IntoSpawnable`] then spawn it, used in [`try_spawn!`].
+#[doc(hidden)]
+#[track_caller]
+pub fn try_spawn_with<T: IntoSpawnable>(f: impl FnOnce() -> T) -> Result<Entity, SpawnError> {
+    if SPAWNER.get().spawner.is_null() {
+        return Err(SpawnError::NoSpawner(Location::caller()));
+    }
+    // for panic safety and nested `try_spawn!`, this will restore the outer value.
+    let reset = ResetMissingAssetServer(MISSING_ASSET_SERVER.replace(Some(None)));
+    let spawned = f();
+    let missing = MISSING_ASSET_SERVER.get().flatten();
+    drop(reset);
+    if let Some(location) = missing {
+        return Err(SpawnError::NoAssetServer(location));
+    }
+    try_spawn(spawned)
 }
 
 /// A type that can be converted into a [`Bundle`].
@@ -110,15 +632,31 @@ pub trait IntoBundle {
 
 /// A type that can be spawned as an entity.
 pub trait Spawnable {
+    /// If true, [`Spawnable::spawn_mut`] and [`Spawnable::spawn_children`] do nothing,
+    /// this enables batched spawning via [`Spawner::spawn_batch`].
+    const IS_STATIC: bool = false;
     /// Collects a static bundle of a concrete type.
     fn into_bundle(self) -> impl Bundle;
-    /// Collect heterogenous components or bundles from a mutable reference of self.
+    /// Spawn the entity with [`Spawnable::into_bundle`] and heterogenous components or bundles.
     ///
-    /// A common thing this might do is [`Option::take`] optional bundles and insert them.
-    fn spawn_mut<'t>(&mut self, spawner: &'t mut Spawner) -> EntityMutSpawner<'t> {
-        spawner.spawn_empty()
+    /// A common thing this might do is match optional bundles and spawn them
+    /// together with the static bundle, so the entity is spawned in its final archetype.
+    ///
+    /// This is called after [`Spawnable::spawn_children`], the entity must be spawned by
+    /// the first [`Spawner::spawn_bundle`] or [`Spawner::spawn_empty`] call on `spawner`.
+    fn spawn_mut<'t>(self, spawner: &'t mut Spawner) -> EntityMutSpawner<'t>
+    where
+        Self: Sized,
+    {
+        spawner.spawn_bundle(self.into_bundle())
     }
     /// Spawn children.
+    ///
+    /// [`spawn!`] can be used here to spawn children, in addition to `spawner`.
+    ///
+    /// Children are spawned before their parent and added to it after [`Spawnable::spawn_mut`].
+    /// If spawning with a [`ChildBuilder`](bevy_hierarchy::ChildBuilder), entities returned
+    /// by `spawner` cannot be modified after spawning.
     #[allow(unused_variables)]
     fn spawn_children(&mut self, spawner: &mut Spawner) {}
 }
@@ -142,6 +680,8 @@ impl<T> Spawnable for T
 where
     T: IntoBundle,
 {
+    const IS_STATIC: bool = true;
+
     fn into_bundle(self) -> impl Bundle {
         IntoBundle::into_bundle(self)
     }
@@ -156,6 +696,81 @@ where
     }
 }
 
+/// Construct a [`Spawnable`] and collect components deferred by `@asset` and `@load`,
+/// used in [`spawn!`] and [`spawn_batch!`].
+///
+/// The result is not an opaque type, so it does not borrow `f`.
+#[doc(hidden)]
+pub fn deferred_assets<S: Spawnable>(f: impl FnOnce() -> S) -> WithDeferred<S> {
+    // for panic safety, this will reset the collector during unwinding.
+    let reset = ResetDeferred(DEFERRED.replace(Some(Vec::new())));
+    let spawnable = f();
+    let deferred = DEFERRED.take().unwrap_or_default();
+    drop(reset);
+    WithDeferred {
+        spawnable,
+        deferred,
+    }
+}
+
+/// A [`Spawnable`] that inserts components deferred by `@asset` and `@load` after spawning.
+#[doc(hidden)]
+pub struct WithDeferred<S> {
+    spawnable: S,
+    deferred: Vec<DeferredInsert>,
+}
+
+impl<S: Spawnable> Spawnable for WithDeferred<S> {
+    fn into_bundle(self) -> impl Bundle {
+        self.spawnable.into_bundle()
+    }
+
+    fn spawn_mut<'t>(self, spawner: &'t mut Spawner) -> EntityMutSpawner<'t> {
+        let mut entity = self.spawnable.spawn_mut(spawner);
+        for insert in self.deferred {
+            insert(&mut entity);
+        }
+        entity
+    }
+
+    fn spawn_children(&mut self, spawner: &mut Spawner) {
+        self.spawnable.spawn_children(spawner);
+    }
+}
+
+/// A [`Spawnable`] that runs a function to spawn additional children with [`spawn!`].
+pub struct WithChildren<S, F> {
+    spawnable: S,
+    children: Option<F>,
+}
+
+impl<S: Spawnable, F: FnOnce()> WithChildren<S, F> {
+    /// Create a [`WithChildren`] from a [`Spawnable`] and a function that spawns children.
+    pub fn new(spawnable: S, children: F) -> Self {
+        WithChildren {
+            spawnable,
+            children: Some(children),
+        }
+    }
+}
+
+impl<S: Spawnable, F: FnOnce()> Spawnable for WithChildren<S, F> {
+    fn into_bundle(self) -> impl Bundle {
+        self.spawnable.into_bundle()
+    }
+
+    fn spawn_mut<'t>(self, spawner: &'t mut Spawner) -> EntityMutSpawner<'t> {
+        self.spawnable.spawn_mut(spawner)
+    }
+
+    fn spawn_children(&mut self, spawner: &mut Spawner) {
+        self.spawnable.spawn_children(spawner);
+        if let Some(children) = self.children.take() {
+            children()
+        }
+    }
+}
+
 /// Create a function scope that can use [`spawn!`] to create children.
 pub trait SpawnChildScope {
     /// Create a function scope that can use [`spawn!`] to create children.
@@ -208,6 +823,7 @@ impl<T: Asset> Component for AddMe<T> {
 pub struct LoadMe<T: Asset> {
     name: Cow<'static, str>,
     settings: Option<Box<dyn FnOnce(&AssetServer, Cow<str>) -> UntypedHandle + Send + Sync>>,
+    tracked: bool,
     p: PhantomData<T>,
 }
 
@@ -216,6 +832,7 @@ impl<T: Asset> Default for LoadMe<T> {
         Self {
             name: Cow::Borrowed(""),
             settings: None,
+            tracked: false,
             p: PhantomData,
         }
     }
@@ -226,6 +843,7 @@ impl<T: Asset> LoadMe<T> {
         LoadMe {
             name: Cow::Borrowed(path),
             settings: None,
+            tracked: false,
             p: PhantomData,
         }
     }
@@ -234,6 +852,7 @@ impl<T: Asset> LoadMe<T> {
         LoadMe {
             name: Cow::Owned(path.into()),
             settings: None,
+            tracked: false,
             p: PhantomData,
         }
     }
@@ -247,9 +866,17 @@ impl<T: Asset> LoadMe<T> {
             settings: Some(Box::new(|assets, name| {
                 UntypedHandle::from(assets.load_with_settings::<T, _>(name.into_owned(), settings))
             })),
+            tracked: false,
             p: PhantomData,
         }
     }
+
+    /// Insert [`AssetLoading<T>`] alongside the handle,
+    /// which is replaced by [`AssetLoaded<T>`] by [`AssetTrackingPlugin<T>`] when finished.
+    pub const fn tracked(mut self) -> Self {
+        self.tracked = true;
+        self
+    }
 }
 
 impl<T: Asset> Component for LoadMe<T> {
@@ -265,6 +892,42 @@ impl<T: Asset> Component for LoadMe<T> {
                 } else {
                     world.resource::<AssetServer>().load(item.name.into_owned())
                 };
+                let mut commands = world.commands();
+                let mut entity = commands.entity(entity);
+                entity.remove::<Self>().insert(handle);
+                if item.tracked {
+                    entity.insert(AssetLoading::<T>::default());
+                }
+                Some(())
+            })();
+        });
+    }
+}
+
+/// [`Component`] that immediately removes itself, loads the underlying folder
+/// and inserts a [`Handle<LoadedFolder>`].
+#[derive(Debug, Default)]
+pub struct LoadFolderMe(Cow<'static, str>);
+
+impl LoadFolderMe {
+    pub const fn new_static(path: &'static str) -> Self {
+        LoadFolderMe(Cow::Borrowed(path))
+    }
+
+    pub fn new(path: impl Into<String>) -> Self {
+        LoadFolderMe(Cow::Owned(path.into()))
+    }
+}
+
+impl Component for LoadFolderMe {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+    fn register_component_hooks(hooks: &mut ComponentHooks) {
+        hooks.on_insert(|mut world, entity, _| {
+            (|| {
+                let path = mem::take(&mut world.get_entity_mut(entity)?.get_mut::<Self>()?.0);
+                let handle = world
+                    .resource::<AssetServer>()
+                    .load_folder(path.into_owned());
                 world
                     .commands()
                     .entity(entity)
@@ -276,41 +939,179 @@ impl<T: Asset> Component for LoadMe<T> {
     }
 }
 
-#[cfg(test)]
-mod test {
-    use bevy::app::App;
-    use bevy_asset::AssetPlugin;
-    use bevy_ecs::{bundle::Bundle, component::Component, system::RunSystemOnce, world::World};
-    use bevy_hierarchy::WorldChildBuilder;
-    use bevy_spawn_fn_derive::{spawner_fn, spawner_system};
-
-    use crate::IntoBundle;
-
-    #[derive(Component)]
-    pub struct A;
-    #[derive(Component)]
-    pub struct B;
-
-    #[derive(Component)]
-    pub struct C;
-
-    #[derive(Bundle)]
-    pub struct Abc {
-        a: A,
-        b: B,
-        c: C,
-    }
+/// [`Component`] that immediately removes itself, loads the underlying paths
+/// and inserts [`Handles<T>`].
+#[derive(Debug)]
+pub struct LoadAllMe<T: Asset> {
+    paths: Vec<AssetPath<'static>>,
+    p: PhantomData<T>,
+}
 
-    #[derive(Debug, Default)]
-    pub struct IntoAbc {
-        a: f32,
-        b: String,
-        c: char,
+impl<T: Asset> Default for LoadAllMe<T> {
+    fn default() -> Self {
+        LoadAllMe {
+            paths: Vec::new(),
+            p: PhantomData,
+        }
     }
+}
 
-    impl IntoBundle for IntoAbc {
-        fn into_bundle(self) -> impl Bundle {
-            Abc { a: A, b: B, c: C }
+impl<T: Asset> LoadAllMe<T> {
+    pub fn new(paths: impl IntoIterator<Item = impl Into<AssetPath<'static>>>) -> Self {
+        LoadAllMe {
+            paths: paths.into_iter().map(Into::into).collect(),
+            p: PhantomData,
+        }
+    }
+}
+
+impl<T: Asset> Component for LoadAllMe<T> {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+    fn register_component_hooks(hooks: &mut ComponentHooks) {
+        hooks.on_insert(|mut world, entity, _| {
+            (|| {
+                let paths = mem::take(&mut world.get_entity_mut(entity)?.get_mut::<Self>()?.paths);
+                let asset_server = world.resource::<AssetServer>();
+                let handles =
+                    Handles::<T>(paths.into_iter().map(|x| asset_server.load(x)).collect());
+                world
+                    .commands()
+                    .entity(entity)
+                    .remove::<Self>()
+                    .insert(handles);
+                Some(())
+            })();
+        });
+    }
+}
+
+/// [`Component`] that immediately removes itself, loads assets matching
+/// the underlying glob pattern and inserts [`Handles<T>`].
+///
+/// Directories are read by the asset reader when inserted, see `@load_glob`.
+#[derive(Debug)]
+pub struct LoadGlobMe<T: Asset> {
+    pattern: Cow<'static, str>,
+    p: PhantomData<T>,
+}
+
+impl<T: Asset> Default for LoadGlobMe<T> {
+    fn default() -> Self {
+        LoadGlobMe {
+            pattern: Cow::Borrowed(""),
+            p: PhantomData,
+        }
+    }
+}
+
+impl<T: Asset> LoadGlobMe<T> {
+    pub const fn new_static(pattern: &'static str) -> Self {
+        LoadGlobMe {
+            pattern: Cow::Borrowed(pattern),
+            p: PhantomData,
+        }
+    }
+
+    pub fn new(pattern: impl Into<String>) -> Self {
+        LoadGlobMe {
+            pattern: Cow::Owned(pattern.into()),
+            p: PhantomData,
+        }
+    }
+}
+
+impl<T: Asset> Component for LoadGlobMe<T> {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+    fn register_component_hooks(hooks: &mut ComponentHooks) {
+        hooks.on_insert(|mut world, entity, _| {
+            (|| {
+                let pattern =
+                    mem::take(&mut world.get_entity_mut(entity)?.get_mut::<Self>()?.pattern);
+                let asset_server = world.resource::<AssetServer>();
+                let handles = Handles::<T>(
+                    glob::glob_asset_paths(asset_server, &pattern)
+                        .into_iter()
+                        .map(|x| asset_server.load(x))
+                        .collect(),
+                );
+                world
+                    .commands()
+                    .entity(entity)
+                    .remove::<Self>()
+                    .insert(handles);
+                Some(())
+            })();
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy::{
+        app::App,
+        core::TaskPoolPlugin,
+        ecs::reflect::AppTypeRegistry,
+        math::Vec2,
+        reflect::{std_traits::ReflectDefault, DynamicStruct, Reflect, TypePath},
+        render::texture::{Image, ImageLoaderSettings},
+    };
+    use bevy_asset::{
+        io::Reader, Asset, AssetApp, AssetLoader, AssetPlugin, Assets, Handle, LoadContext,
+        LoadedFolder,
+    };
+    use bevy_ecs::{
+        archetype::ArchetypeId,
+        bundle::Bundle,
+        component::{Component, ComponentHooks, ComponentId, StorageType},
+        entity::Entity,
+        reflect::{ReflectBundle, ReflectComponent},
+        system::{Commands, Resource, RunSystemOnce},
+        world::{DeferredWorld, World},
+    };
+    use bevy_hierarchy::{BuildChildren, ChildBuilder, Parent, WorldChildBuilder};
+    use bevy_hierarchy::{BuildWorldChildren, Children};
+    use bevy_spawn_fn_derive::{spawner_fn, spawner_system};
+    use serde::{Deserialize, Serialize};
+    use std::{
+        marker::PhantomData,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    use crate::{
+        resolve_handles_hook, spawner_scope, with_world, AsSpawner, AssetServer, HandleResolver,
+        HandleSlot, Handles, IntoBundle, ReflectSpawnable, Res, ResolveHandles, SpawnChildScope,
+        SpawnError, Spawnable, Spawner,
+    };
+
+    #[derive(Component, Reflect, Default, Clone)]
+    #[reflect(Component)]
+    pub struct A;
+    #[derive(Component, Reflect, Default, Clone)]
+    #[reflect(Component)]
+    pub struct B;
+
+    #[derive(Component, Reflect, Default, Clone)]
+    #[reflect(Component)]
+    pub struct C;
+
+    #[derive(Bundle)]
+    pub struct Abc {
+        a: A,
+        b: B,
+        c: C,
+    }
+
+    #[derive(Debug, Default)]
+    #[allow(dead_code)]
+    pub struct IntoAbc {
+        a: f32,
+        b: String,
+        c: char,
+    }
+
+    impl IntoBundle for IntoAbc {
+        fn into_bundle(self) -> impl Bundle {
+            Abc { a: A, b: B, c: C }
         }
     }
 
@@ -341,10 +1142,1002 @@ mod test {
         });
     }
 
+    #[derive(Component, Default)]
+    pub struct Pair<T: Send + Sync + 'static, U: Send + Sync + 'static>(PhantomData<(T, U)>);
+
+    #[derive(Spawnable, Default)]
+    pub struct DeriveAbc {
+        a: A,
+        #[spawnable(optional)]
+        b: Option<B>,
+        #[spawnable(children)]
+        c: Vec<C>,
+        #[spawnable(skip)]
+        #[allow(dead_code)]
+        name: String,
+    }
+
+    #[test]
+    fn derive_spawnable() {
+        let mut world = World::new();
+        let entity = spawner_scope(&mut world, || {
+            spawn!(DeriveAbc {
+                a: A,
+                b: @some B,
+                c: @arr [C, C],
+                name: "Ferris",
+            })
+        });
+        let entity = world.entity(entity);
+        assert!(entity.contains::<A>());
+        assert!(entity.contains::<B>());
+        let children = entity.get::<Children>().unwrap();
+        assert_eq!(children.len(), 2);
+        assert!(children.iter().all(|c| world.entity(*c).contains::<C>()));
+    }
+
+    #[derive(Component, Debug, PartialEq)]
+    pub struct Health(f32);
+
+    impl From<f32> for Health {
+        fn from(value: f32) -> Self {
+            Health(value)
+        }
+    }
+
+    fn make_c(_: char) -> C {
+        C
+    }
+
+    #[derive(IntoBundle, Default)]
+    pub struct DescribeAbc {
+        #[bundle(component)]
+        a: A,
+        #[bundle(into = Health)]
+        health: f32,
+        #[bundle(with = make_c)]
+        c: char,
+        #[bundle(skip)]
+        #[allow(dead_code)]
+        name: String,
+    }
+
+    #[test]
+    fn derive_into_bundle() {
+        let mut world = World::new();
+        let entity = spawner_scope(&mut world, || {
+            spawn!(DescribeAbc {
+                health: 4,
+                c: 'c',
+                name: "Ferris",
+            })
+        });
+        let entity = world.entity(entity);
+        assert!(entity.contains::<A>());
+        assert!(!entity.contains::<B>());
+        assert!(entity.contains::<C>());
+        assert_eq!(entity.get::<Health>(), Some(&Health(4.0)));
+    }
+
+    #[derive(IntoBundle, Default)]
+    pub struct UnannotatedBundle {
+        a: A,
+        #[bundle(into = Health)]
+        health: f32,
+        pair: (B, C),
+    }
+
+    #[derive(Spawnable, Default)]
+    pub struct UnannotatedSpawnable {
+        a: A,
+        #[spawnable(children)]
+        children: Vec<C>,
+        pair: (B, C),
+    }
+
+    #[test]
+    fn derive_unannotated_fields() {
+        let mut world = World::new();
+        let [bundle, spawnable] = spawner_scope(&mut world, || {
+            [
+                spawn!(UnannotatedBundle { health: 2 }),
+                spawn!(UnannotatedSpawnable { children: @arr [C] }),
+            ]
+        });
+        for entity in [bundle, spawnable] {
+            let entity = world.entity(entity);
+            assert!(entity.contains::<A>());
+            assert!(entity.contains::<B>());
+            assert!(entity.contains::<C>());
+        }
+        assert_eq!(world.entity(bundle).get::<Health>(), Some(&Health(2.0)));
+        assert_children(&world, spawnable, 1);
+    }
+
+    #[derive(Component, Default)]
+    #[allow(dead_code)]
+    pub struct Texture {
+        handle: Handle<Image>,
+    }
+
+    #[derive(Component, Default)]
+    #[allow(dead_code)]
+    pub struct Sprites {
+        frames: Vec<Handle<Image>>,
+        target: Option<Entity>,
+    }
+
+    #[test]
+    fn try_spawn() {
+        assert!(matches!(try_spawn!(A), Err(SpawnError::NoSpawner(_))));
+        let mut world = World::new();
+        spawner_scope(&mut world, || {
+            assert!(matches!(
+                try_spawn!(Sprites {
+                    frames: @load_glob "*.png"
+                }),
+                Err(SpawnError::NoAssetServer(_))
+            ));
+            // a nested `try_spawn!` does not clear the error.
+            assert!(matches!(
+                try_spawn!(Sprites {
+                    frames: @load_glob "*.png",
+                    target: try_spawn!(A).ok(),
+                }),
+                Err(SpawnError::NoAssetServer(_))
+            ));
+            assert!(try_spawn!(A).is_ok());
+        });
+        assert_eq!(world.entities().len(), 2);
+    }
+
+    pub struct Nested(usize);
+
+    impl Spawnable for Nested {
+        fn into_bundle(self) -> impl Bundle {
+            A
+        }
+
+        fn spawn_children(&mut self, spawner: &mut Spawner) {
+            let mut entity = spawner.spawn_bundle(B);
+            if self.0 > 0 {
+                spawn!(Nested(self.0 - 1));
+            }
+            entity.insert(C);
+            spawn!(C);
+        }
+    }
+
+    fn assert_nested(world: &World, entity: Entity, depth: usize) {
+        let children = world.entity(entity).get::<Children>().unwrap();
+        if depth == 0 {
+            assert_eq!(children.len(), 2);
+        } else {
+            assert_eq!(children.len(), 3);
+            assert_nested(world, children[1], depth - 1);
+        }
+        assert!(world.entity(children[0]).contains::<B>());
+        assert!(world.entity(children[0]).contains::<C>());
+        assert!(world.entity(children[children.len() - 1]).contains::<C>());
+    }
+
+    #[test]
+    fn reentrant_spawn() {
+        let mut world = World::new();
+        let entity = spawner_scope(&mut world, || spawn!(Nested(2)));
+        assert_nested(&world, entity, 2);
+
+        #[spawner_fn]
+        fn spawn_nested(spawner: &mut Commands) -> Entity {
+            spawn!(Nested(2))
+        }
+        let entity = world.run_system_once(|mut commands: Commands| spawn_nested(&mut commands));
+        assert_nested(&world, entity, 2);
+    }
+
+    pub fn assert_children(world: &World, entity: Entity, len: usize) -> Vec<Entity> {
+        let children = world.entity(entity).get::<Children>().unwrap();
+        assert_eq!(children.len(), len);
+        children.to_vec()
+    }
+
+    #[spawner_fn]
+    fn spawn_declarative(spawner: &mut Commands) -> Entity {
+        spawn!(A => [
+            B => [
+                C,
+                C,
+            ],
+            C
+        ])
+    }
+
+    #[test]
+    fn declarative_children() {
+        let mut world = World::new();
+        let root = world.run_system_once(|mut commands: Commands| spawn_declarative(&mut commands));
+        let children = assert_children(&world, root, 2);
+        assert!(world.entity(children[0]).contains::<B>());
+        assert!(world.entity(children[1]).contains::<C>());
+        assert_children(&world, children[0], 2);
+
+        let root = spawner_scope(&mut world, || spawn!(Nested(0) => [A, B]));
+        let children = assert_children(&world, root, 4);
+        assert!(world.entity(children[2]).contains::<A>());
+        assert!(world.entity(children[3]).contains::<B>());
+
+        let root = spawner_scope(&mut world, || {
+            spawn!(A => [
+                Pair::<A, B>::default(),
+                Pair::<Pair<A, B>, C>::default() => [C],
+                (B, C),
+            ])
+        });
+        let children = assert_children(&world, root, 3);
+        assert!(world.entity(children[0]).contains::<Pair<A, B>>());
+        assert!(world.entity(children[1]).contains::<Pair<Pair<A, B>, C>>());
+        assert_children(&world, children[1], 1);
+        assert!(world.entity(children[2]).contains::<C>());
+
+        spawner_scope(&mut world, || {
+            let root = try_spawn!(A => [
+                Pair::<A, B>::default(),
+                Sprites {
+                    frames: @load_glob "*.png"
+                },
+            ]);
+            assert!(matches!(root, Err(SpawnError::NoAssetServer(_))));
+        });
+    }
+
+    #[spawner_fn]
+    fn spawn_batches(spawner: &mut Commands) -> Vec<Entity> {
+        let mut entities = spawn_batch!(for c in ['a', 'b'] => DescribeAbc {
+            health: 1,
+            c: c,
+        });
+        entities.extend(spawn_batch!([Nested(0), Nested(1)]));
+        entities
+    }
+
+    #[test]
+    fn batch_spawn() {
+        let mut world = World::new();
+        let entities = spawner_scope(&mut world, || spawn_batch!([A, A, A]));
+        assert_eq!(entities.len(), 3);
+        assert!(entities.iter().all(|e| world.entity(*e).contains::<A>()));
+
+        let entities = world.run_system_once(|mut commands: Commands| spawn_batches(&mut commands));
+        assert_eq!(entities.len(), 4);
+        assert_eq!(
+            world.entity(entities[1]).get::<Health>(),
+            Some(&Health(1.0))
+        );
+        assert_nested(&world, entities[3], 1);
+
+        let root = spawner_scope(&mut world, || {
+            crate::spawn(crate::WithChildren::new(B, || {
+                spawn_batch!([C, C]);
+            }))
+        });
+        assert_children(&world, root, 2);
+
+        // Reserved and spawned by one `insert_or_spawn_batch` command.
+        let mut world = move_world();
+        let entities = world.run_system_once(|mut commands: Commands| {
+            spawner_scope(&mut commands, || spawn_batch!(for _ in 0..3 => A))
+        });
+        assert_eq!(entities.len(), 3);
+        assert_single_move(&world, &entities);
+    }
+
+    /// Archetypes entities were in when a hooked component was inserted,
+    /// each distinct archetype of an entity is one archetype move.
+    #[derive(Resource, Default)]
+    pub struct Moves(Vec<(Entity, ArchetypeId)>);
+
+    fn record_move(mut world: DeferredWorld, entity: Entity, _: ComponentId) {
+        let archetype = world.entity(entity).archetype().id();
+        let moves = &mut world.resource_mut::<Moves>().0;
+        if !moves.contains(&(entity, archetype)) {
+            moves.push((entity, archetype));
+        }
+    }
+
+    /// A [`World`] that records moves on every component used in move tests,
+    /// including `Parent` and `Children`.
+    fn move_world() -> World {
+        let mut world = World::new();
+        world.init_resource::<Moves>();
+        world.register_component_hooks::<A>().on_insert(record_move);
+        world.register_component_hooks::<B>().on_insert(record_move);
+        world.register_component_hooks::<C>().on_insert(record_move);
+        world
+            .register_component_hooks::<Parent>()
+            .on_insert(record_move);
+        world
+            .register_component_hooks::<Children>()
+            .on_insert(record_move);
+        world
+    }
+
+    /// Assert entities moved `count` times and are still in the last recorded archetype.
+    #[track_caller]
+    fn assert_moves(world: &World, entities: &[Entity], count: usize) {
+        let moves = &world.resource::<Moves>().0;
+        for entity in entities {
+            let archetypes: Vec<_> = moves
+                .iter()
+                .filter(|(e, _)| e == entity)
+                .map(|(_, archetype)| *archetype)
+                .collect();
+            assert_eq!(archetypes.len(), count);
+            assert_eq!(
+                archetypes.last(),
+                Some(&world.entity(*entity).archetype().id())
+            );
+        }
+    }
+
+    #[track_caller]
+    fn assert_single_move(world: &World, entities: &[Entity]) {
+        assert_moves(world, entities, 1);
+    }
+
+    #[test]
+    fn single_archetype_move() {
+        // Moves caused by `Parent` and `Children`, which are inserted by `bevy_hierarchy`
+        // after spawning, are counted separately.
+        let mut world = move_world();
+        let entity = spawner_scope(&mut world, || {
+            spawn!(DeriveAbc {
+                a: A,
+                b: @some B,
+                c: [C, C],
+            })
+        });
+        let children = assert_children(&world, entity, 2);
+        // Spawned, then `Children` or `Parent`.
+        assert_moves(&world, &[entity], 2);
+        assert_moves(&world, &children, 2);
+
+        let single = spawner_scope(&mut world, || spawn!(DeriveAbc { a: A, b: @some B }));
+        assert_single_move(&world, &[single]);
+
+        let mut world = move_world();
+        let entity = world.run_system_once(|mut commands: Commands| {
+            spawner_scope(&mut commands, || spawn!((A, B) => [C, A => [B, C]]))
+        });
+        let children = assert_children(&world, entity, 2);
+        let grandchildren = assert_children(&world, children[1], 2);
+        assert_moves(&world, &[entity, children[0]], 2);
+        // Spawned, then `Children`, then `Parent`.
+        assert_moves(&world, &children[1..], 3);
+        assert_moves(&world, &grandchildren, 2);
+
+        // Static items are spawned with `Parent` by the builder.
+        let mut world = move_world();
+        let mut parent = world.spawn_empty();
+        parent.with_children(|builder| {
+            spawner_scope(builder, || {
+                spawn!(A);
+                spawn!(A => [B, C]);
+            });
+        });
+        let parent = parent.id();
+        let children = assert_children(&world, parent, 2);
+        let grandchildren = assert_children(&world, children[1], 2);
+        assert_single_move(&world, &children[..1]);
+        // Reserved with `Parent`, then spawned, then `Children`.
+        assert_moves(&world, &children[1..], 3);
+        assert_moves(&world, &grandchildren, 2);
+
+        let mut world = move_world();
+        let parent = world.run_system_once(|mut commands: Commands| {
+            commands
+                .spawn_empty()
+                .with_children(|builder| {
+                    spawner_scope(builder, || {
+                        spawn!(A);
+                        spawn!(A => [B, C]);
+                    });
+                })
+                .id()
+        });
+        let children = assert_children(&world, parent, 2);
+        let grandchildren = assert_children(&world, children[1], 2);
+        // Spawned, then `Parent`.
+        assert_moves(&world, &children[..1], 2);
+        // Spawned, then `Children`, then `Parent`.
+        assert_moves(&world, &children[1..], 3);
+        assert_moves(&world, &grandchildren, 2);
+    }
+
+    fn is_static<S: Spawnable>(_: &S) -> bool {
+        S::IS_STATIC
+    }
+
+    #[test]
+    fn macro_static_items() {
+        // Only items with asset effects collect deferred components,
+        // so others can be batched or spawned by child builders directly.
+        assert!(is_static(&spawn!(@__spawnable A)));
+        assert!(is_static(&spawn!(@__spawnable ImageBundle {
+            image: Handle::default()
+        })));
+        assert!(!is_static(&spawn!(@__spawnable ImageBundle {
+            image: @load "a.png"
+        })));
+
+        let mut world = move_world();
+        let mut parent = world.spawn_empty();
+        parent.with_children(|builder| {
+            spawner_scope(builder, || {
+                spawn!(A);
+                spawn_batch!(for _ in 0..2 => A);
+            });
+        });
+        let parent = parent.id();
+        let children = assert_children(&world, parent, 3);
+        assert_single_move(&world, &children);
+    }
+
+    #[test]
+    fn builder_children() {
+        #[spawner_fn]
+        fn spawn_in_builder(spawner: &mut ChildBuilder) {
+            spawn!(A);
+            spawn!(Nested(1));
+            spawn!(B => [C]);
+        }
+
+        fn assert_hierarchy(world: &World, parent: Entity) {
+            let children = assert_children(world, parent, 3);
+            assert!(world.entity(children[0]).contains::<A>());
+            assert_nested(world, children[1], 1);
+            let grandchildren = assert_children(world, children[2], 1);
+            for &child in &children {
+                assert_eq!(world.entity(child).get::<Parent>().unwrap().get(), parent);
+            }
+            assert_eq!(
+                world
+                    .entity(grandchildren[0])
+                    .get::<Parent>()
+                    .unwrap()
+                    .get(),
+                children[2]
+            );
+        }
+
+        let mut world = World::new();
+        let parent = world.spawn_empty().id();
+        world.run_system_once(move |mut commands: Commands| {
+            commands.entity(parent).with_children(spawn_in_builder);
+        });
+        assert_hierarchy(&world, parent);
+
+        let parent = world.run_system_once(|mut commands: Commands| {
+            let mut entity = commands.spawn_empty();
+            entity.spawn_child_scope(|| {
+                spawn!(A);
+                spawn!(Nested(1));
+                spawn!(B => [C]);
+            });
+            entity.id()
+        });
+        assert_hierarchy(&world, parent);
+
+        let mut parent = world.spawn_empty();
+        parent.with_children(|builder| {
+            spawner_scope(builder, || {
+                spawn!(A);
+                spawn!(Nested(1));
+                spawn!(B => [C]);
+            })
+        });
+        let parent = parent.id();
+        assert_hierarchy(&world, parent);
+    }
+
+    #[spawner_fn(spawner = commands, asset_server = assets)]
+    fn spawn_named(commands: &mut Commands, assets: Res<AssetServer>) -> Entity {
+        spawn!(Texture {
+            handle: @load "circle.png"
+        })
+    }
+
+    #[spawner_fn]
+    fn spawn_typed(mut spawner: Commands, server: &AssetServer) -> Entity {
+        spawn!(Texture {
+            handle: @load "circle.png"
+        })
+    }
+
+    #[test]
+    fn spawner_fn_params() {
+        let mut app = test_app();
+        let world = app.world_mut();
+        let entity = world.run_system_once(|mut commands: Commands, assets: Res<AssetServer>| {
+            spawn_named(&mut commands, assets)
+        });
+        let handle = &world.entity(entity).get::<Texture>().unwrap().handle;
+        assert_ne!(handle, &Handle::default());
+        assert_eq!(handle.path().unwrap().to_string(), "circle.png");
+        let entity = world.run_system_once(|commands: Commands, assets: Res<AssetServer>| {
+            spawn_typed(commands, &assets)
+        });
+        let handle = &world.entity(entity).get::<Texture>().unwrap().handle;
+        assert_ne!(handle, &Handle::default());
+        assert_eq!(
+            world.resource::<AssetServer>().get_path(handle.id()),
+            Some("circle.png".into())
+        );
+    }
+
+    pub trait SpawnExt {
+        fn spawn_abc(&mut self) -> Entity;
+    }
+
+    impl SpawnExt for World {
+        #[spawner_fn]
+        fn spawn_abc(&mut self) -> Entity {
+            spawn!(A => [B, C])
+        }
+    }
+
+    impl SpawnExt for Commands<'_, '_> {
+        #[spawner_fn]
+        fn spawn_abc(&mut self) -> Entity {
+            spawn!(A => [B, C])
+        }
+    }
+
+    pub trait SpawnDefault: for<'t> AsSpawner<'t, 't, 't> {
+        #[spawner_fn]
+        fn spawn_default(&mut self) -> Entity {
+            spawn!(A)
+        }
+    }
+
+    impl SpawnDefault for World {}
+
+    #[test]
+    fn spawner_fn_self() {
+        let mut world = World::new();
+        let entity = world.spawn_abc();
+        assert_children(&world, entity, 2);
+        let entity = world.run_system_once(|mut commands: Commands| commands.spawn_abc());
+        assert_children(&world, entity, 2);
+        let entity = world.spawn_default();
+        assert!(world.entity(entity).contains::<A>());
+    }
+
+    #[spawner_system]
+    fn spawn_exclusive(world: &mut World) -> usize {
+        let entity = spawn!(A => [B, C]);
+        with_world(|world| world.entity(entity).get::<Children>().unwrap().len()).unwrap()
+    }
+
+    #[spawner_system(exclusive)]
+    fn spawn_exclusive_injected() -> Entity {
+        spawn!(A)
+    }
+
+    #[test]
+    fn exclusive_system() {
+        let mut app = test_app();
+        let world = app.world_mut();
+        assert_eq!(world.run_system_once(spawn_exclusive), 2);
+        let entity = world.run_system_once(spawn_exclusive_injected);
+        assert!(world.entity(entity).contains::<A>());
+        assert_eq!(with_world(|_| ()), None);
+    }
+
+    #[spawner_system]
+    fn spawn_headless() -> Entity {
+        spawn!(A => [B])
+    }
+
+    #[spawner_system]
+    fn spawn_headless_exclusive(_: &mut World) -> Result<Entity, SpawnError> {
+        try_spawn!(Sprites {
+            frames: @load_glob "*.png"
+        })
+    }
+
+    #[test]
+    fn optional_asset_server() {
+        let mut world = World::new();
+        let entity = world.run_system_once(spawn_headless);
+        assert_children(&world, entity, 1);
+        assert!(matches!(
+            world.run_system_once(spawn_headless_exclusive),
+            Err(SpawnError::NoAssetServer(_))
+        ));
+    }
+
+    #[derive(Bundle, Default)]
+    pub struct ImageBundle {
+        pub a: A,
+        pub image: Handle<Image>,
+    }
+
+    #[spawner_fn]
+    fn spawn_deferred(spawner: &mut World) -> [Entity; 3] {
+        [
+            spawn!(ImageBundle {
+                image: @load "missing.png"
+            }),
+            try_spawn!(ImageBundle {
+                image: @load "missing.png"
+            })
+            .unwrap(),
+            spawn!(ImageBundle {
+                image: @asset Image::default()
+            } => [B]),
+        ]
+    }
+
+    #[test]
+    fn deferred_assets() {
+        let mut app = test_app();
+        let world = app.world_mut();
+        let [loaded, try_loaded, added] = spawn_deferred(world);
+        world.flush_commands();
+        for loaded in [loaded, try_loaded] {
+            let handle = world.entity(loaded).get::<Handle<Image>>().unwrap();
+            assert!(handle.is_strong());
+            assert_eq!(handle.path().unwrap().to_string(), "missing.png");
+            assert!(!world.entity(loaded).contains::<crate::LoadMe<Image>>());
+        }
+        let handle = world.entity(added).get::<Handle<Image>>().unwrap();
+        assert!(world.resource::<Assets<Image>>().contains(handle));
+        assert!(!world.entity(added).contains::<crate::AddMe<Image>>());
+        assert_children(world, added, 1);
+    }
+
+    #[spawner_fn]
+    fn spawn_deferred_batch(spawner: &mut World) -> Vec<Entity> {
+        spawn_batch!(for path in ["a.png", "b.png"] => ImageBundle {
+            image: @load path
+        })
+    }
+
+    #[test]
+    fn deferred_assets_batch() {
+        let mut app = test_app();
+        let world = app.world_mut();
+        let entities = spawn_deferred_batch(world);
+        world.flush_commands();
+        for (entity, path) in entities.into_iter().zip(["a.png", "b.png"]) {
+            let handle = world.entity(entity).get::<Handle<Image>>().unwrap();
+            assert_eq!(handle.path().unwrap().to_string(), path);
+        }
+    }
+
+    #[spawner_fn]
+    fn spawn_deferred_field(spawner: &mut World) -> Entity {
+        spawn!(Texture {
+            handle: @load "missing.png"
+        })
+    }
+
+    #[test]
+    #[should_panic(expected = "handles must be components")]
+    fn deferred_assets_field() {
+        let mut app = test_app();
+        spawn_deferred_field(app.world_mut());
+    }
+
+    #[derive(Default)]
+    pub struct Material {
+        pub texture: HandleSlot<Image>,
+        pub layers: Vec<HandleSlot<Image>>,
+    }
+
+    impl ResolveHandles for Material {
+        fn resolve_handles(&mut self, resolver: &mut HandleResolver) {
+            self.texture.resolve_handles(resolver);
+            self.layers.resolve_handles(resolver);
+        }
+    }
+
+    impl Component for Material {
+        const STORAGE_TYPE: StorageType = StorageType::Table;
+        fn register_component_hooks(hooks: &mut ComponentHooks) {
+            hooks.on_insert(resolve_handles_hook::<Self>);
+        }
+    }
+
+    #[spawner_fn]
+    fn spawn_load_with(spawner: &mut World) -> [Entity; 2] {
+        [
+            spawn!(ImageBundle {
+                image: @load_with "image.png", |s: &mut ImageLoaderSettings| s.is_srgb = false,
+                a: A,
+            }),
+            spawn!(Material {
+                texture: @load_with ("texture.png", |s: &mut ImageLoaderSettings| s.is_srgb = false)
+            }),
+        ]
+    }
+
+    #[test]
+    fn load_with_settings() {
+        let mut app = test_app();
+        let world = app.world_mut();
+        let [image, material] = spawn_load_with(world);
+        world.flush_commands();
+        let handle = world.entity(image).get::<Handle<Image>>().unwrap();
+        assert_eq!(handle.path().unwrap().to_string(), "image.png");
+        let material = world.entity(material).get::<Material>().unwrap();
+        let handle = material.texture.handle().unwrap();
+        assert_eq!(handle.path().unwrap().to_string(), "texture.png");
+
+        let asset_server = world.resource::<AssetServer>().clone();
+        let handle: Handle<Image> = crate::asset_server_scope(&asset_server, || {
+            crate::load_with_settings("scoped.png", |s: &mut ImageLoaderSettings| {
+                s.is_srgb = false
+            })
+        });
+        assert_eq!(handle.path().unwrap().to_string(), "scoped.png");
+    }
+
+    #[derive(Asset, Reflect)]
+    pub struct Value(pub u32);
+
+    #[derive(Default, Serialize, Deserialize, TypePath)]
+    pub struct ValueSettings {
+        pub value: u32,
+    }
+
+    #[derive(Default)]
+    pub struct ValueLoader;
+
+    impl AssetLoader for ValueLoader {
+        type Asset = Value;
+        type Settings = ValueSettings;
+        type Error = std::io::Error;
+
+        async fn load<'a>(
+            &'a self,
+            _: &'a mut Reader<'_>,
+            settings: &'a ValueSettings,
+            _: &'a mut LoadContext<'_>,
+        ) -> Result<Value, std::io::Error> {
+            Ok(Value(settings.value))
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["value"]
+        }
+    }
+
+    /// An app that loads [`Image`] and [`Value`] from `tests/assets`.
+    pub fn test_app() -> App {
+        let mut app = App::new();
+        add_test_plugins(&mut app);
+        app
+    }
+
+    /// Add the plugins of [`test_app`] to an existing app.
+    pub fn add_test_plugins(app: &mut App) {
+        app.add_plugins((
+            TaskPoolPlugin::default(),
+            AssetPlugin {
+                file_path: "tests/assets".into(),
+                ..Default::default()
+            },
+        ));
+        app.init_asset::<Image>();
+        app.init_asset::<Value>();
+        app.register_asset_loader(ValueLoader);
+    }
+
+    /// Update until `done` returns true, panics if it takes too long.
+    pub fn update_until(app: &mut App, mut done: impl FnMut(&World) -> bool) {
+        for _ in 0..1000 {
+            app.update();
+            if done(app.world()) {
+                return;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+        panic!("Not done after 1000 updates.");
+    }
+
+    #[derive(Bundle, Default)]
+    pub struct ValueBundle {
+        pub a: A,
+        pub value: Handle<Value>,
+    }
+
+    static SETTINGS_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+    #[spawner_fn]
+    fn spawn_value(spawner: &mut World) -> Entity {
+        spawn!(ValueBundle {
+            value: @load_with "one.value", |s: &mut ValueSettings| {
+                SETTINGS_CALLS.fetch_add(1, Ordering::Relaxed);
+                s.value = 1;
+            },
+        })
+    }
+
+    #[spawner_fn]
+    fn spawn_value_scoped(spawner: &mut World, asset_server: &AssetServer) -> Entity {
+        spawn!(ValueBundle {
+            value: @load_with "two.value", move |s: &mut ValueSettings| s.value = 2,
+        })
+    }
+
+    #[test]
+    fn load_with_loader() {
+        let mut app = test_app();
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        let deferred = spawn_value(app.world_mut());
+        let scoped = spawn_value_scoped(app.world_mut(), &asset_server);
+        app.world_mut().flush_commands();
+        let handles = [deferred, scoped]
+            .map(|entity| app.world().get::<Handle<Value>>(entity).unwrap().clone());
+        update_until(&mut app, |_| {
+            handles
+                .iter()
+                .all(|handle| asset_server.is_loaded_with_dependencies(handle))
+        });
+        assert_eq!(SETTINGS_CALLS.load(Ordering::Relaxed), 1);
+        let values = app.world().resource::<Assets<Value>>();
+        assert_eq!(handles.map(|handle| values.get(&handle).unwrap().0), [1, 2]);
+    }
+
+    #[derive(Component, Default)]
+    pub struct Frames {
+        pub frames: Vec<Handle<Image>>,
+        pub glob: Vec<Handle<Value>>,
+    }
+
+    #[derive(Bundle, Default)]
+    pub struct FolderBundle {
+        pub a: A,
+        pub folder: Handle<LoadedFolder>,
+    }
+
+    #[derive(Bundle, Default)]
+    pub struct HandlesBundle {
+        pub a: A,
+        pub handles: Handles<Value>,
+    }
+
+    #[spawner_system(check_assets)]
+    fn spawn_checked() -> Entity {
+        spawn!(ImageBundle {
+            image: @load "circle.png"
+        });
+        spawn!(Frames {
+            frames: @load_all ["circle.png", "circle.png#Label"],
+        })
+    }
+
+    #[test]
+    fn check_assets() {
+        let mut app = test_app();
+        let entity = app.world_mut().run_system_once(spawn_checked);
+        let frames = app.world().entity(entity).get::<Frames>().unwrap();
+        assert_eq!(frames.frames.len(), 2);
+    }
+
+    #[spawner_system(check_assets = "tests/assets")]
+    fn spawn_checked_dir() -> Entity {
+        spawn!(ValueBundle {
+            value: @load "frames/sub/2.value"
+        })
+    }
+
+    #[test]
+    fn check_assets_dir() {
+        let mut app = test_app();
+        let entity = app.world_mut().run_system_once(spawn_checked_dir);
+        let handle = app.world().entity(entity).get::<Handle<Value>>().unwrap();
+        assert_eq!(handle.path().unwrap().to_string(), "frames/sub/2.value");
+    }
+
+    #[derive(Debug, Component, Reflect, Default, Clone, PartialEq)]
+    #[reflect(Component)]
+    pub struct Hitpoints(pub u32);
+
+    #[derive(Debug, Default, PartialEq, Reflect)]
+    pub enum Kind {
+        #[default]
+        Normal,
+        Boss {
+            level: u32,
+        },
+        Named(String),
+    }
+
+    #[derive(Debug, Component, Reflect, Default, PartialEq)]
+    #[reflect(Component, Default)]
+    pub struct Stats {
+        pub speed: f32,
+        pub size: Option<Vec2>,
+        pub kind: Kind,
+        pub tags: Vec<String>,
+        pub image: Handle<Image>,
+        pub frames: Vec<Handle<Value>>,
+    }
+
+    #[derive(Debug, Component, Reflect, Default)]
+    #[reflect(Component, Default)]
+    pub struct ValueHandle(pub Handle<Value>);
+
+    #[derive(Bundle, Reflect, Default)]
+    #[reflect(Bundle, Default)]
+    pub struct StatsBundle {
+        pub stats: Stats,
+        pub hitpoints: Hitpoints,
+    }
+
+    #[derive(Reflect)]
+    #[reflect(Spawnable)]
+    pub struct Squad {
+        size: usize,
+    }
+
+    impl Spawnable for Squad {
+        fn into_bundle(self) -> impl Bundle {
+            A
+        }
+
+        fn spawn_children(&mut self, spawner: &mut Spawner) {
+            for _ in 0..self.size {
+                spawner.spawn(Hitpoints(1));
+            }
+        }
+    }
+
+    #[test]
+    fn reflect_spawnable() {
+        let mut world = World::new();
+        world.init_resource::<AppTypeRegistry>();
+        world
+            .resource::<AppTypeRegistry>()
+            .write()
+            .register::<Squad>();
+
+        let mut squad = DynamicStruct::default();
+        squad.insert("size", 3usize);
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        let registry = registry.read();
+        let reflect_spawnable = registry
+            .get_with_short_type_path("Squad")
+            .and_then(|x| x.data::<ReflectSpawnable>())
+            .unwrap();
+        let entity = reflect_spawnable.spawn(&squad, &mut world.as_spawner());
+        assert!(world.entity(entity).contains::<A>());
+        let children = world.entity(entity).get::<Children>().unwrap();
+        assert_eq!(children.len(), 3);
+        assert!(children
+            .iter()
+            .all(|x| world.entity(*x).get::<Hitpoints>() == Some(&Hitpoints(1))));
+
+        let entities = world.entities().len();
+        let err = reflect_spawnable
+            .try_spawn(&Hitpoints(1), &mut world.as_spawner())
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            SpawnError::FromReflect {
+                expected: "bevy_spawn_fn::test::Squad",
+                found: Some("bevy_spawn_fn::test::Hitpoints"),
+                ..
+            }
+        ));
+        assert_eq!(err.location().file(), file!());
+        assert_eq!(world.entities().len(), entities);
+    }
+
     #[test]
     fn miri_test() {
-        let mut world = App::new();
-        world.add_plugins(AssetPlugin::default());
-        world.world_mut().run_system_once(test3);
+        test_app().world_mut().run_system_once(test3);
     }
 }
@@ -0,0 +1,218 @@
+use bevy_app::{App, Plugin};
+use bevy_asset::{meta::Settings, Asset, AssetServer, Handle};
+use bevy_ecs::{
+    component::{Component, ComponentHooks, StorageType},
+    reflect::ReflectComponent,
+};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect, TypePath};
+use bevy_utils::tracing::error;
+use serde::de::DeserializeOwned;
+use std::{any::TypeId, marker::PhantomData};
+
+/// Reflectable version of [`LoadMe`](crate::LoadMe) that can be used in scene files.
+///
+/// Immediately removes itself, loads `path` and inserts a [`Handle<T>`].
+/// If `settings` is not empty, it is deserialized from `ron` as loader settings `S`,
+/// invalid settings, or any settings if `S` is `()`, are logged and the default settings are used.
+///
+/// Register with [`LoadOnInsertPlugin<T, S>`].
+///
+/// ```text
+/// "bevy_spawn_fn::LoadOnInsert<bevy_render::texture::image::Image, ()>": (
+///     path: "circle.png",
+///     settings: "",
+/// ),
+/// ```
+///
+/// Logs an error if the world has no [`AssetServer`].
+#[derive(Debug, Reflect)]
+#[reflect(Component, Default)]
+#[type_path = "bevy_spawn_fn"]
+pub struct LoadOnInsert<T: Asset, S: Settings + TypePath + DeserializeOwned = ()> {
+    /// Path of the asset.
+    pub path: String,
+    /// Loader settings `S` in `ron`, ignored if empty.
+    pub settings: String,
+    #[reflect(ignore)]
+    p: PhantomData<(T, S)>,
+}
+
+impl<T: Asset, S: Settings + TypePath + DeserializeOwned> Default for LoadOnInsert<T, S> {
+    fn default() -> Self {
+        LoadOnInsert {
+            path: String::new(),
+            settings: String::new(),
+            p: PhantomData,
+        }
+    }
+}
+
+impl<T: Asset, S: Settings + TypePath + DeserializeOwned> LoadOnInsert<T, S> {
+    pub fn new(path: impl Into<String>) -> Self {
+        LoadOnInsert {
+            path: path.into(),
+            settings: String::new(),
+            p: PhantomData,
+        }
+    }
+
+    pub fn new_with_settings(path: impl Into<String>, settings: impl Into<String>) -> Self {
+        LoadOnInsert {
+            path: path.into(),
+            settings: settings.into(),
+            p: PhantomData,
+        }
+    }
+}
+
+impl<T: Asset, S: Settings + TypePath + DeserializeOwned> Component for LoadOnInsert<T, S> {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+    fn register_component_hooks(hooks: &mut ComponentHooks) {
+        hooks.on_insert(|mut world, entity, _| {
+            (|| {
+                let item =
+                    std::mem::take(world.get_entity_mut(entity)?.get_mut::<Self>()?.as_mut());
+                let Some(asset_server) = world.get_resource::<AssetServer>() else {
+                    error!("Cannot load {} without an AssetServer.", item.path);
+                    return None;
+                };
+                let handle: Handle<T> = if item.settings.is_empty() {
+                    asset_server.load(item.path)
+                } else if TypeId::of::<S>() == TypeId::of::<()>() {
+                    error!(
+                        "Settings for {} are ignored, specify the loader settings type `S`.",
+                        item.path
+                    );
+                    asset_server.load(item.path)
+                } else {
+                    let (path, settings) = (item.path.clone(), item.settings);
+                    // Settings are applied on every load, including reloads.
+                    asset_server.load_with_settings(
+                        item.path,
+                        move |s: &mut S| match ron::from_str(&settings) {
+                            Ok(value) => *s = value,
+                            Err(err) => error!("Invalid settings for {path}: {err}."),
+                        },
+                    )
+                };
+                world
+                    .commands()
+                    .entity(entity)
+                    .remove::<Self>()
+                    .insert(handle);
+                Some(())
+            })();
+        });
+    }
+}
+
+/// A [`Plugin`] that registers [`LoadOnInsert<T, S>`] and [`Handle<T>`] for reflection.
+pub struct LoadOnInsertPlugin<T: Asset, S: Settings + TypePath + DeserializeOwned = ()>(
+    PhantomData<(T, S)>,
+);
+
+impl<T: Asset, S: Settings + TypePath + DeserializeOwned> Default for LoadOnInsertPlugin<T, S> {
+    fn default() -> Self {
+        LoadOnInsertPlugin(PhantomData)
+    }
+}
+
+impl<T: Asset, S: Settings + TypePath + DeserializeOwned> Plugin for LoadOnInsertPlugin<T, S> {
+    fn build(&self, app: &mut App) {
+        app.register_type::<LoadOnInsert<T, S>>()
+            .register_type::<Handle<T>>();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy::{
+        ecs::{entity::EntityHashMap, reflect::AppTypeRegistry},
+        reflect::TypePath,
+        render::texture::Image,
+        scene::serde::SceneDeserializer,
+    };
+    use bevy_asset::{Assets, Handle};
+    use bevy_ecs::world::World;
+    use serde::de::DeserializeSeed;
+
+    use crate::{test::*, LoadOnInsert, LoadOnInsertPlugin};
+
+    #[test]
+    fn load_on_insert_scene() {
+        let mut app = test_app();
+        app.add_plugins(LoadOnInsertPlugin::<Image>::default());
+        let type_path = LoadOnInsert::<Image>::type_path();
+        assert_eq!(
+            type_path,
+            "bevy_spawn_fn::LoadOnInsert<bevy_render::texture::image::Image, ()>"
+        );
+        let scene = format!(
+            r#"(
+                resources: {{}},
+                entities: {{
+                    4294967296: (
+                        components: {{
+                            "{type_path}": (path: "circle.png", settings: ""),
+                        }},
+                    ),
+                }},
+            )"#
+        );
+        let world = app.world_mut();
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        let scene = SceneDeserializer {
+            type_registry: &registry.read(),
+        }
+        .deserialize(&mut ron::Deserializer::from_str(&scene).unwrap())
+        .unwrap();
+        let mut entities = EntityHashMap::default();
+        scene.write_to_world(world, &mut entities).unwrap();
+        world.flush_commands();
+        let entity = *entities.values().next().unwrap();
+        let handle = world.entity(entity).get::<Handle<Image>>().unwrap();
+        assert_eq!(handle.path().unwrap().to_string(), "circle.png");
+        assert!(!world.entity(entity).contains::<LoadOnInsert<Image>>());
+    }
+
+    #[test]
+    fn load_on_insert_settings() {
+        let mut app = test_app();
+        let world = app.world_mut();
+        let entities = [
+            world
+                .spawn(LoadOnInsert::<Value, ValueSettings>::new_with_settings(
+                    "one.value",
+                    "(value: 3)",
+                ))
+                .id(),
+            world
+                .spawn(LoadOnInsert::<Value, ValueSettings>::new_with_settings(
+                    "two.value",
+                    "(valu: 3)",
+                ))
+                .id(),
+            world
+                .spawn(LoadOnInsert::<Value>::new_with_settings(
+                    "frames/0.value",
+                    "(value: 3)",
+                ))
+                .id(),
+        ];
+        world.flush_commands();
+        let handles = entities.map(|x| world.entity(x).get::<Handle<Value>>().unwrap().clone());
+        update_until(&mut app, |world| {
+            let assets = world.resource::<Assets<Value>>();
+            handles.iter().all(|x| assets.contains(x))
+        });
+        let assets = app.world().resource::<Assets<Value>>();
+        let values = handles.map(|x| assets.get(&x).unwrap().0);
+        // Invalid settings and settings without a settings type use the default.
+        assert_eq!(values, [3, 0, 0]);
+
+        let mut world = World::new();
+        let entity = world.spawn(LoadOnInsert::<Value>::new("one.value")).id();
+        world.flush_commands();
+        assert!(!world.entity(entity).contains::<Handle<Value>>());
+    }
+}
@@ -0,0 +1,772 @@
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_asset::{
+    io::Reader, Asset, AssetApp, AssetEvent, AssetLoader, Assets, AsyncReadExt, Handle, LoadContext,
+};
+use bevy_ecs::{
+    bundle::Bundle,
+    component::{Component, ComponentId},
+    entity::Entity,
+    event::{Events, ManualEventReader},
+    query::{QueryState, Without},
+    reflect::{AppTypeRegistry, ReflectBundle, ReflectComponent},
+    schedule::IntoSystemConfigs,
+    system::Local,
+    world::{EntityWorldMut, FromWorld, Mut, World},
+};
+use bevy_hierarchy::{BuildWorldChildren, Children, DespawnRecursiveExt};
+use bevy_reflect::{
+    serde::TypedReflectDeserializer, Reflect, ReflectFromReflect, TypePath, TypeRegistry,
+    TypeRegistryArc,
+};
+use bevy_utils::tracing::warn;
+use serde::{
+    de::{self, DeserializeSeed, Deserializer, MapAccess, SeqAccess, Visitor},
+    Deserialize,
+};
+use std::{
+    borrow::Cow,
+    cell::RefCell,
+    fmt::{self, Display},
+    sync::Arc,
+};
+
+use crate::{EntityMutSpawner, IntoBundle, Spawnable, Spawner};
+
+/// An entity tree loaded from a `.prefab.ron` file.
+///
+/// Components are deserialized through the [`AppTypeRegistry`] and must be registered
+/// with `#[reflect(Component)]`, either by their full or short type path.
+///
+/// ```text
+/// (
+///     components: {
+///         "Transform": (translation: (x: 0.0, y: 1.0, z: 0.0)),
+///         "my_game::Health": (10),
+///     },
+///     children: [
+///         (
+///             name: "weapon",
+///             components: { "my_game::Damage": (2) },
+///         ),
+///     ],
+/// )
+/// ```
+///
+/// Spawning a [`Handle<Prefab>`] instantiates the prefab on the same entity once loaded,
+/// this requires [`PrefabPlugin`].
+///
+/// ```ignore
+/// spawn!(asset_server.load::<Prefab>("enemies/goblin.prefab.ron"))
+/// ```
+#[derive(Debug, Asset, TypePath)]
+pub struct Prefab {
+    /// The root entity.
+    pub root: PrefabNode,
+}
+
+/// An entity in a [`Prefab`].
+#[derive(Debug, Default)]
+pub struct PrefabNode {
+    /// Name of the entity, used to address children.
+    pub name: Option<String>,
+    /// Reflected components or bundles of the entity.
+    pub components: Vec<Box<dyn Reflect>>,
+    /// Children of the entity.
+    pub children: Vec<PrefabNode>,
+}
+
+impl PrefabNode {
+    /// Insert components and spawn children on an entity.
+    ///
+    /// Returns named descendants by their path, e.g. `shield/boss`.
+    pub fn spawn_on(
+        &self,
+        entity: &mut EntityWorldMut,
+        registry: &TypeRegistry,
+    ) -> Vec<(String, Entity)> {
+        for component in &self.components {
+            insert_reflect(entity, component.as_ref(), registry);
+        }
+        let named = RefCell::new(Vec::new());
+        let root = NodeSpawnable {
+            node: self,
+            registry,
+            path: Some(String::new()),
+            named: &named,
+        };
+        let children: Vec<_> = entity.world_scope(|world| {
+            let mut spawner = Spawner::World(world);
+            self.children
+                .iter()
+                .map(|child| spawner.spawn(root.child(child)))
+                .collect()
+        });
+        if !children.is_empty() {
+            entity.push_children(&children);
+        }
+        named.into_inner()
+    }
+}
+
+/// Insert a reflected component or bundle, warns if the type is neither.
+fn insert_reflect(entity: &mut EntityWorldMut, component: &dyn Reflect, registry: &TypeRegistry) {
+    let Some(info) = component.get_represented_type_info() else {
+        warn!(
+            "Prefab component `{}` does not represent a type.",
+            component.reflect_type_path()
+        );
+        return;
+    };
+    if let Some(reflect_component) = registry.get_type_data::<ReflectComponent>(info.type_id()) {
+        reflect_component.insert(entity, component, registry);
+    } else if let Some(reflect_bundle) = registry.get_type_data::<ReflectBundle>(info.type_id()) {
+        reflect_bundle.insert(entity, component, registry);
+    } else {
+        warn!(
+            "Prefab component `{}` is not a reflected component or bundle.",
+            info.type_path()
+        );
+    }
+}
+
+/// Insert reflected components on any [`EntityMutSpawner`].
+fn insert_components(
+    entity: &mut EntityMutSpawner,
+    components: &[Box<dyn Reflect>],
+    registry: &TypeRegistry,
+) {
+    match entity {
+        EntityMutSpawner::EntityWorldMut(entity) => {
+            for component in components {
+                insert_reflect(entity, component.as_ref(), registry);
+            }
+        }
+        EntityMutSpawner::EntityCommands(entity) => {
+            let components: Vec<_> = components.iter().map(|x| x.clone_value()).collect();
+            entity.add(move |mut entity: EntityWorldMut| {
+                let registry = entity.world().resource::<AppTypeRegistry>().clone();
+                let registry = registry.read();
+                for component in &components {
+                    insert_reflect(&mut entity, component.as_ref(), &registry);
+                }
+            });
+        }
+        EntityMutSpawner::Scoped(entity) => {
+            entity.entity_mut_scope(&mut |entity| insert_components(entity, components, registry))
+        }
+    }
+}
+
+/// A [`Spawnable`] that spawns a [`PrefabNode`] and its children.
+struct NodeSpawnable<'a> {
+    node: &'a PrefabNode,
+    registry: &'a TypeRegistry,
+    /// Path of the node, `None` if not addressable.
+    path: Option<String>,
+    named: &'a RefCell<Vec<(String, Entity)>>,
+}
+
+impl<'a> NodeSpawnable<'a> {
+    fn child(&self, child: &'a PrefabNode) -> NodeSpawnable<'a> {
+        // Descendants of unnamed children are not addressable.
+        let path = match (&child.name, self.path.as_deref()) {
+            (Some(name), Some("")) => Some(name.clone()),
+            (Some(name), Some(path)) => Some(format!("{path}/{name}")),
+            _ => None,
+        };
+        NodeSpawnable {
+            node: child,
+            registry: self.registry,
+            path,
+            named: self.named,
+        }
+    }
+}
+
+impl Spawnable for NodeSpawnable<'_> {
+    fn into_bundle(self) -> impl Bundle {}
+
+    fn spawn_mut<'t>(self, spawner: &'t mut Spawner) -> EntityMutSpawner<'t> {
+        let mut entity = spawner.spawn_empty();
+        insert_components(&mut entity, &self.node.components, self.registry);
+        if let Some(path) = self.path {
+            self.named.borrow_mut().push((path, entity.id()));
+        }
+        entity
+    }
+
+    fn spawn_children(&mut self, spawner: &mut Spawner) {
+        for child in &self.node.children {
+            spawner.spawn(self.child(child));
+        }
+    }
+}
+
+/// Error loading a [`Prefab`].
+#[derive(Debug)]
+pub enum PrefabLoadError {
+    Io(std::io::Error),
+    Ron(ron::error::SpannedError),
+}
+
+impl Display for PrefabLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PrefabLoadError::Io(err) => write!(f, "Could not read prefab: {err}"),
+            PrefabLoadError::Ron(err) => write!(f, "Could not parse prefab: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for PrefabLoadError {}
+
+impl From<std::io::Error> for PrefabLoadError {
+    fn from(value: std::io::Error) -> Self {
+        PrefabLoadError::Io(value)
+    }
+}
+
+impl From<ron::error::SpannedError> for PrefabLoadError {
+    fn from(value: ron::error::SpannedError) -> Self {
+        PrefabLoadError::Ron(value)
+    }
+}
+
+/// [`AssetLoader`] for `.prefab.ron` files.
+pub struct PrefabLoader {
+    registry: TypeRegistryArc,
+}
+
+impl FromWorld for PrefabLoader {
+    fn from_world(world: &mut World) -> Self {
+        PrefabLoader {
+            registry: world.resource::<AppTypeRegistry>().0.clone(),
+        }
+    }
+}
+
+impl AssetLoader for PrefabLoader {
+    type Asset = Prefab;
+    type Settings = ();
+    type Error = PrefabLoadError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _: &'a (),
+        _: &'a mut LoadContext<'_>,
+    ) -> Result<Prefab, PrefabLoadError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let registry = self.registry.read();
+        let mut deserializer = ron::Deserializer::from_bytes(&bytes)?;
+        let root = NodeDeserializer {
+            registry: &registry,
+        }
+        .deserialize(&mut deserializer)
+        .map_err(|err| deserializer.span_error(err))?;
+        deserializer
+            .end()
+            .map_err(|err| deserializer.span_error(err))?;
+        Ok(Prefab { root })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["prefab.ron"]
+    }
+}
+
+const NODE_FIELDS: &[&str] = &["name", "components", "children"];
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum NodeField {
+    Name,
+    Components,
+    Children,
+}
+
+#[derive(Clone, Copy)]
+struct NodeDeserializer<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'de> DeserializeSeed<'de> for NodeDeserializer<'_> {
+    type Value = PrefabNode;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<PrefabNode, D::Error> {
+        deserializer.deserialize_struct("PrefabNode", NODE_FIELDS, self)
+    }
+}
+
+impl<'de> Visitor<'de> for NodeDeserializer<'_> {
+    type Value = PrefabNode;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a prefab node")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<PrefabNode, A::Error> {
+        let mut node = PrefabNode::default();
+        while let Some(key) = map.next_key()? {
+            match key {
+                NodeField::Name => node.name = Some(map.next_value()?),
+                NodeField::Components => {
+                    node.components = map.next_value_seed(ComponentsDeserializer(self))?
+                }
+                NodeField::Children => {
+                    node.children = map.next_value_seed(ChildrenDeserializer(self))?
+                }
+            }
+        }
+        Ok(node)
+    }
+}
+
+struct ComponentsDeserializer<'a>(NodeDeserializer<'a>);
+
+impl<'de> DeserializeSeed<'de> for ComponentsDeserializer<'_> {
+    type Value = Vec<Box<dyn Reflect>>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for ComponentsDeserializer<'_> {
+    type Value = Vec<Box<dyn Reflect>>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a map of components")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let registry = self.0.registry;
+        let mut components = Vec::new();
+        while let Some(key) = map.next_key::<String>()? {
+            let registration = registry
+                .get_with_type_path(&key)
+                .or_else(|| registry.get_with_short_type_path(&key))
+                .ok_or_else(|| de::Error::custom(format!("Type `{key}` is not registered.")))?;
+            if registration.data::<ReflectComponent>().is_none() {
+                return Err(de::Error::custom(format!(
+                    "Type `{key}` is not a reflected component."
+                )));
+            }
+            let value =
+                map.next_value_seed(TypedReflectDeserializer::new(registration, registry))?;
+            let value = registration
+                .data::<ReflectFromReflect>()
+                .and_then(|x| x.from_reflect(value.as_ref()))
+                .unwrap_or(value);
+            components.push(value);
+        }
+        Ok(components)
+    }
+}
+
+struct ChildrenDeserializer<'a>(NodeDeserializer<'a>);
+
+impl<'de> DeserializeSeed<'de> for ChildrenDeserializer<'_> {
+    type Value = Vec<PrefabNode>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de> Visitor<'de> for ChildrenDeserializer<'_> {
+    type Value = Vec<PrefabNode>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a list of prefab nodes")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut children = Vec::new();
+        while let Some(child) = seq.next_element_seed(self.0)? {
+            children.push(child);
+        }
+        Ok(children)
+    }
+}
+
+/// Marker for an entity whose [`Handle<Prefab>`] has been instantiated,
+/// the entity is instantiated again in place if the [`Prefab`] is modified.
+#[derive(Debug, Component)]
+pub struct SpawnedFromPrefab(pub Handle<Prefab>);
+
+/// Components and children of the root entity created by its [`Prefab`].
+#[derive(Debug, Component)]
+struct PrefabParts {
+    /// Concrete components, including the components of reflected bundles.
+    components: Vec<ComponentId>,
+    children: Vec<Entity>,
+}
+
+type OverrideFn = Arc<dyn Fn(&mut EntityMutSpawner) + Send + Sync>;
+
+/// Components inserted on top of a [`Prefab`] after it is instantiated,
+/// on the root or on named children addressed by path, e.g. `shield/boss`.
+///
+/// Overrides replace components of the same type in the prefab,
+/// bundles are cloned since overrides are kept on the root entity.
+#[derive(Default, Clone, Component)]
+pub struct PrefabOverrides(Vec<(Cow<'static, str>, OverrideFn)>);
+
+impl PrefabOverrides {
+    /// Create an empty [`PrefabOverrides`].
+    pub fn new() -> Self {
+        PrefabOverrides(Vec::new())
+    }
+
+    /// Insert a bundle on the root entity.
+    pub fn root(self, bundle: impl IntoBundle + Clone + Send + Sync + 'static) -> Self {
+        self.child("", bundle)
+    }
+
+    /// Insert a bundle on a named child by its path, an empty path is the root entity.
+    pub fn child(
+        mut self,
+        path: impl Into<Cow<'static, str>>,
+        bundle: impl IntoBundle + Clone + Send + Sync + 'static,
+    ) -> Self {
+        self.0.push((
+            path.into(),
+            Arc::new(move |entity| entity.insert(bundle.clone().into_bundle())),
+        ));
+        self
+    }
+
+    fn apply(&self, world: &mut World, root: Entity, named: &[(String, Entity)]) {
+        for (path, f) in &self.0 {
+            let entity = if path.is_empty() {
+                Some(root)
+            } else {
+                named.iter().find(|(p, _)| p == path).map(|(_, e)| *e)
+            };
+            let Some(entity) = entity else {
+                warn!("Prefab override path `{path}` not found.");
+                continue;
+            };
+            f(&mut EntityMutSpawner::EntityWorldMut(
+                world.entity_mut(entity),
+            ));
+        }
+    }
+}
+
+/// A [`Spawnable`] that instantiates a [`Prefab`] with [`PrefabOverrides`].
+///
+/// ```ignore
+/// spawn!(PrefabInstance {
+///     prefab: @load "enemies/goblin.prefab.ron",
+///     overrides: PrefabOverrides::new()
+///         .root(Health(20))
+///         .child("weapon", Damage(5)),
+/// })
+/// ```
+#[derive(Default, Clone)]
+pub struct PrefabInstance {
+    /// The prefab to instantiate.
+    pub prefab: Handle<Prefab>,
+    /// Components applied on top of the prefab.
+    pub overrides: PrefabOverrides,
+}
+
+impl Spawnable for PrefabInstance {
+    const IS_STATIC: bool = true;
+
+    fn into_bundle(self) -> impl Bundle {
+        (self.prefab, self.overrides)
+    }
+}
+
+/// A [`Plugin`] that loads `.prefab.ron` files and instantiates [`Handle<Prefab>`]s.
+#[derive(Debug, Default)]
+pub struct PrefabPlugin;
+
+impl Plugin for PrefabPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<Prefab>()
+            .init_asset_loader::<PrefabLoader>()
+            .add_systems(PreUpdate, (reload_prefabs, spawn_prefabs).chain());
+    }
+}
+
+/// Reset entities spawned from modified prefabs, so they can be instantiated again.
+fn reload_prefabs(
+    world: &mut World,
+    mut reader: Local<ManualEventReader<AssetEvent<Prefab>>>,
+    query: &mut QueryState<(Entity, &SpawnedFromPrefab)>,
+) {
+    let modified: Vec<_> = reader
+        .read(world.resource::<Events<AssetEvent<Prefab>>>())
+        .filter_map(|event| match event {
+            AssetEvent::Modified { id } => Some(*id),
+            _ => None,
+        })
+        .collect();
+    if modified.is_empty() {
+        return;
+    }
+    let entities: Vec<_> = query
+        .iter(world)
+        .filter(|(_, spawned)| modified.contains(&spawned.0.id()))
+        .map(|(entity, _)| entity)
+        .collect();
+    for entity in entities {
+        let Some(parts) = world.entity_mut(entity).take::<PrefabParts>() else {
+            continue;
+        };
+        for child in parts.children {
+            if let Some(child) = world.get_entity_mut(child) {
+                child.despawn_recursive();
+            }
+        }
+        let mut entity = world.entity_mut(entity);
+        entity.remove::<SpawnedFromPrefab>();
+        for id in parts.components {
+            entity.remove_by_id(id);
+        }
+    }
+}
+
+fn spawn_prefabs(
+    world: &mut World,
+    query: &mut QueryState<(Entity, &Handle<Prefab>), Without<SpawnedFromPrefab>>,
+) {
+    let prefabs = world.resource::<Assets<Prefab>>();
+    let loaded: Vec<_> = query
+        .iter(world)
+        .filter(|(_, handle)| prefabs.contains(*handle))
+        .map(|(entity, handle)| (entity, handle.clone()))
+        .collect();
+    if loaded.is_empty() {
+        return;
+    }
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    world.resource_scope(|world, prefabs: Mut<Assets<Prefab>>| {
+        let children_id = world.init_component::<Children>();
+        for (entity, handle) in loaded {
+            let Some(prefab) = prefabs.get(&handle) else {
+                continue;
+            };
+            let mut entity_mut = world.entity_mut(entity);
+            let children = entity_mut
+                .get::<Children>()
+                .map(|x| x.to_vec())
+                .unwrap_or_default();
+            let existing: Vec<_> = entity_mut.archetype().components().collect();
+            let named = prefab.root.spawn_on(&mut entity_mut, &registry);
+            // Bundles are only known by the components they added, components
+            // the entity already had are replaced by the prefab.
+            let mut components: Vec<_> = entity_mut
+                .archetype()
+                .components()
+                .filter(|x| *x != children_id && !existing.contains(x))
+                .collect();
+            for component in &prefab.root.components {
+                let id = component
+                    .get_represented_type_info()
+                    .and_then(|x| entity_mut.world().components().get_id(x.type_id()));
+                if let Some(id) = id.filter(|x| !components.contains(x)) {
+                    components.push(id);
+                }
+            }
+            let parts = PrefabParts {
+                components,
+                children: entity_mut
+                    .get::<Children>()
+                    .into_iter()
+                    .flatten()
+                    .filter(|x| !children.contains(x))
+                    .copied()
+                    .collect(),
+            };
+            entity_mut.insert((SpawnedFromPrefab(handle), parts));
+            if let Some(overrides) = entity_mut.get::<PrefabOverrides>().cloned() {
+                overrides.apply(world, entity, &named);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use bevy::app::App;
+    use bevy_asset::{AssetServer, Assets};
+    use bevy_ecs::{entity::Entity, world::World};
+    use bevy_hierarchy::{BuildWorldChildren, Children};
+    use bevy_spawn_fn_derive::spawner_fn;
+
+    use crate::{
+        spawn, spawner_scope, test::*, Prefab, PrefabInstance, PrefabNode, PrefabOverrides,
+        PrefabPlugin, SpawnedFromPrefab,
+    };
+
+    fn prefab_app() -> App {
+        let mut app = test_app();
+        app.register_type::<Hitpoints>();
+        app.add_plugins(PrefabPlugin);
+        app
+    }
+
+    #[test]
+    fn prefab() {
+        let mut app = prefab_app();
+        let world = app.world_mut();
+        let handle = world
+            .resource::<AssetServer>()
+            .load::<Prefab>("goblin.prefab.ron");
+        let goblin = spawner_scope(world, || crate::spawn((A, handle.clone())));
+        // Components may use their short type path.
+        let imp = world
+            .resource::<AssetServer>()
+            .load::<Prefab>("imp.prefab.ron");
+        let imp = world.spawn(imp).id();
+        update_until(&mut app, |world| {
+            world.entity(goblin).contains::<Hitpoints>()
+                && world.entity(imp).contains::<Hitpoints>()
+        });
+        let world = app.world();
+        let prefab = world.resource::<Assets<Prefab>>().get(&handle).unwrap();
+        assert_eq!(prefab.root.children[0].name.as_deref(), Some("weapon"));
+        assert_eq!(
+            world.entity(goblin).get::<Hitpoints>(),
+            Some(&Hitpoints(10))
+        );
+        assert!(world.entity(goblin).contains::<A>());
+        let children = world.entity(goblin).get::<Children>().unwrap();
+        assert_eq!(children.len(), 2);
+        assert_eq!(
+            world.entity(children[0]).get::<Hitpoints>(),
+            Some(&Hitpoints(2))
+        );
+        assert_children(world, children[1], 1);
+        assert_eq!(world.entity(imp).get::<Hitpoints>(), Some(&Hitpoints(3)));
+        app.update();
+        assert_children(app.world(), goblin, 2);
+    }
+
+    #[spawner_fn]
+    fn spawn_goblin(spawner: &mut World) -> Entity {
+        spawn!(PrefabInstance {
+            prefab: @load "goblin.prefab.ron",
+            overrides: PrefabOverrides::new()
+                .root((A, Hitpoints(20)))
+                .child("weapon", (B, Hitpoints(7)))
+                .child("shield/missing", C),
+        })
+    }
+
+    #[test]
+    fn prefab_overrides() {
+        let mut app = prefab_app();
+        let goblin = spawn_goblin(app.world_mut());
+        update_until(&mut app, |world| {
+            world.entity(goblin).contains::<Hitpoints>()
+        });
+        let world = app.world();
+        assert_eq!(
+            world.entity(goblin).get::<Hitpoints>(),
+            Some(&Hitpoints(20))
+        );
+        assert!(world.entity(goblin).contains::<A>());
+        let children = assert_children(world, goblin, 2);
+        assert_eq!(
+            world.entity(children[0]).get::<Hitpoints>(),
+            Some(&Hitpoints(7))
+        );
+        assert!(world.entity(children[0]).contains::<B>());
+        assert!(!world.entity(children[1]).contains::<C>());
+    }
+
+    #[test]
+    fn prefab_hot_reload() {
+        let mut app = prefab_app();
+        let goblin = spawn_goblin(app.world_mut());
+        update_until(&mut app, |world| {
+            world.entity(goblin).contains::<SpawnedFromPrefab>()
+        });
+        let world = app.world_mut();
+        let handle = world
+            .entity(goblin)
+            .get::<SpawnedFromPrefab>()
+            .unwrap()
+            .0
+            .clone();
+        let plain = world
+            .spawn((A, handle.clone()))
+            .with_children(|b| {
+                b.spawn(C);
+            })
+            .id();
+        app.update();
+        let world = app.world_mut();
+        assert_eq!(world.entity(plain).get::<Hitpoints>(), Some(&Hitpoints(10)));
+        let old_children = assert_children(world, plain, 3);
+
+        let mut prefabs = world.resource_mut::<Assets<Prefab>>();
+        let prefab = prefabs.get_mut(&handle).unwrap();
+        prefab.root.components.clear();
+        prefab.root.children.truncate(1);
+        prefab.root.children.push(PrefabNode {
+            components: vec![Box::new(Hitpoints(99))],
+            ..Default::default()
+        });
+        // `AssetEvent`s are sent at the end of the frame.
+        app.update();
+        app.update();
+
+        let world = app.world();
+        assert!(!world.entity(plain).contains::<Hitpoints>());
+        assert!(world.entity(plain).contains::<A>());
+        let children = assert_children(world, plain, 3);
+        assert_eq!(children[0], old_children[0]);
+        assert!(world.get_entity(old_children[1]).is_none());
+        assert!(world.get_entity(old_children[2]).is_none());
+        assert_eq!(
+            world.entity(children[2]).get::<Hitpoints>(),
+            Some(&Hitpoints(99))
+        );
+
+        assert_eq!(
+            world.entity(goblin).get::<Hitpoints>(),
+            Some(&Hitpoints(20))
+        );
+        let children = assert_children(world, goblin, 2);
+        assert_eq!(
+            world.entity(children[0]).get::<Hitpoints>(),
+            Some(&Hitpoints(7))
+        );
+    }
+
+    #[test]
+    fn prefab_bundle_hot_reload() {
+        let mut app = prefab_app();
+        app.register_type::<StatsBundle>();
+        let world = app.world_mut();
+        let handle = world.resource_mut::<Assets<Prefab>>().add(Prefab {
+            root: PrefabNode {
+                components: vec![Box::new(StatsBundle::default())],
+                ..Default::default()
+            },
+        });
+        let entity = world.spawn((A, handle.clone())).id();
+        app.update();
+        let world = app.world_mut();
+        assert!(world.entity(entity).contains::<Stats>());
+        assert!(world.entity(entity).contains::<Hitpoints>());
+
+        let mut prefabs = world.resource_mut::<Assets<Prefab>>();
+        prefabs.get_mut(&handle).unwrap().root.components = vec![Box::new(Hitpoints(5))];
+        app.update();
+        app.update();
+        let world = app.world();
+        assert!(!world.entity(entity).contains::<Stats>());
+        assert_eq!(world.entity(entity).get::<Hitpoints>(), Some(&Hitpoints(5)));
+        assert!(world.entity(entity).contains::<A>());
+    }
+}
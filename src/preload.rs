@@ -0,0 +1,165 @@
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_asset::{AssetServer, Handle, LoadedUntypedAsset};
+use bevy_ecs::{
+    schedule::IntoSystemConfigs,
+    system::{Res, ResMut, Resource},
+};
+
+use crate::load_finished;
+
+/// A [`Plugin`] that loads asset manifests generated by
+/// `#[spawner_system(manifest)]` or `#[spawner_fn(manifest)]` on startup.
+///
+/// Progress can be checked via the [`PreloadedAssets`] resource,
+/// which is inserted when the app is finished.
+///
+/// ```ignore
+/// #[spawner_system(manifest)]
+/// fn spawn_level() { .. }
+///
+/// app.add_plugins(PreloadPlugin::new([spawn_level::ASSETS]))
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct PreloadPlugin {
+    paths: Vec<&'static str>,
+}
+
+impl PreloadPlugin {
+    /// Create a [`PreloadPlugin`] from manifests.
+    pub fn new(manifests: impl IntoIterator<Item = &'static [&'static str]>) -> Self {
+        PreloadPlugin::default().with_manifests(manifests)
+    }
+
+    /// Add manifests to the [`PreloadPlugin`].
+    pub fn with_manifests(
+        mut self,
+        manifests: impl IntoIterator<Item = &'static [&'static str]>,
+    ) -> Self {
+        for path in manifests.into_iter().flatten() {
+            if !self.paths.contains(path) {
+                self.paths.push(path);
+            }
+        }
+        self
+    }
+}
+
+impl Plugin for PreloadPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(PreUpdate, update_preloaded_assets.run_if(assets_loading));
+    }
+
+    /// Load assets in `finish`, since the [`AssetServer`] may be added after this plugin.
+    fn finish(&self, app: &mut App) {
+        let asset_server = app.world().resource::<AssetServer>();
+        let handles = self
+            .paths
+            .iter()
+            .map(|path| asset_server.load_untyped(*path))
+            .collect();
+        app.insert_resource(PreloadedAssets {
+            handles,
+            loaded: 0,
+            failed: 0,
+        });
+    }
+}
+
+/// [`Resource`] that tracks assets loaded by [`PreloadPlugin`].
+#[derive(Debug, Resource)]
+pub struct PreloadedAssets {
+    handles: Vec<Handle<LoadedUntypedAsset>>,
+    loaded: usize,
+    failed: usize,
+}
+
+impl PreloadedAssets {
+    /// Returns the number of assets loaded or failed, and the total number of assets.
+    pub fn progress(&self) -> (usize, usize) {
+        (self.loaded + self.failed, self.handles.len())
+    }
+
+    /// Returns the number of assets that failed to load.
+    pub fn failed(&self) -> usize {
+        self.failed
+    }
+
+    /// Returns true if all assets are loaded or failed.
+    pub fn is_done(&self) -> bool {
+        self.loaded + self.failed == self.handles.len()
+    }
+
+    /// Returns true if all assets are loaded successfully.
+    pub fn is_loaded(&self) -> bool {
+        self.loaded == self.handles.len()
+    }
+}
+
+/// Run condition that returns true if all assets in [`PreloadedAssets`] are loaded or failed.
+pub fn assets_preloaded(assets: Option<Res<PreloadedAssets>>) -> bool {
+    assets.is_some_and(|x| x.is_done())
+}
+
+fn assets_loading(assets: Res<PreloadedAssets>) -> bool {
+    !assets.is_done()
+}
+
+fn update_preloaded_assets(asset_server: Res<AssetServer>, mut assets: ResMut<PreloadedAssets>) {
+    let (mut loaded, mut failed) = (0, 0);
+    for handle in &assets.handles {
+        match load_finished(&asset_server, handle) {
+            Some(false) => loaded += 1,
+            Some(true) => failed += 1,
+            None => (),
+        }
+    }
+    assets.loaded = loaded;
+    assets.failed = failed;
+}
+
+#[cfg(test)]
+mod test {
+    use bevy::app::App;
+    use bevy_spawn_fn_derive::spawner_system;
+
+    use crate::{spawn, test::*, PreloadPlugin, PreloadedAssets};
+
+    #[spawner_system(manifest)]
+    fn spawn_manifest() {
+        spawn!(ValueBundle {
+            value: @load "one.value"
+        });
+        spawn!(HandlesBundle {
+            handles: @load_all ["one.value", "frames/0.value"],
+        });
+    }
+
+    /// Update until [`PreloadedAssets::is_done`].
+    fn preload(manifests: &[&'static [&'static str]]) -> App {
+        let mut app = App::new();
+        // Added before `AssetPlugin`, since assets are loaded in `finish`.
+        app.add_plugins(PreloadPlugin::new(manifests.iter().copied()));
+        add_test_plugins(&mut app);
+        app.finish();
+        update_until(&mut app, |world| {
+            world.resource::<PreloadedAssets>().is_done()
+        });
+        app
+    }
+
+    #[test]
+    fn preload_manifest() {
+        assert_eq!(spawn_manifest::ASSETS, ["one.value", "frames/0.value"]);
+        let app = preload(&[spawn_manifest::ASSETS]);
+        let assets = app.world().resource::<PreloadedAssets>();
+        assert_eq!(assets.progress(), (2, 2));
+        assert_eq!(assets.failed(), 0);
+        assert!(assets.is_loaded());
+
+        let app = preload(&[spawn_manifest::ASSETS, &["missing.value"]]);
+        let assets = app.world().resource::<PreloadedAssets>();
+        assert_eq!(assets.progress(), (3, 3));
+        assert_eq!(assets.failed(), 1);
+        assert!(!assets.is_loaded());
+    }
+}
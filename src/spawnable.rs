@@ -5,8 +5,10 @@ use bevy_ecs::{
     world::{EntityWorldMut, World},
 };
 use bevy_hierarchy::{BuildChildren, BuildWorldChildren, ChildBuilder, WorldChildBuilder};
+use bevy_reflect::{FromReflect, FromType, Reflect, TypePath};
+use std::{cell::Cell, panic::Location};
 
-use crate::{IntoSpawnable, SpawnChildScope, Spawnable};
+use crate::{push_spawner, with_spawner, IntoSpawnable, SpawnChildScope, SpawnError, Spawnable};
 
 /// A type that can spawn [`Bundle`]s.
 pub trait AsSpawner<'t, 'a, 'b> {
@@ -86,6 +88,19 @@ impl<'t> EntityMutSpawner<'t> {
         }
     }
 
+    /// Add children to the entity.
+    pub(crate) fn add_children(&mut self, children: &[Entity]) {
+        match self {
+            EntityMutSpawner::EntityWorldMut(x) => {
+                x.push_children(children);
+            }
+            EntityMutSpawner::EntityCommands(x) => {
+                x.push_children(children);
+            }
+            EntityMutSpawner::Scoped(x) => x.entity_mut_scope(&mut |x| x.add_children(children)),
+        }
+    }
+
     pub fn id(&self) -> Entity {
         match self {
             EntityMutSpawner::EntityWorldMut(x) => x.id(),
@@ -113,10 +128,16 @@ impl<'t> EntityMutSpawner<'t> {
 
 impl Spawner<'_, '_, '_> {
     /// Spawn a empty [`Entity`] with a spawner.
-    pub fn spawn_empty(&mut self) -> EntityMutSpawner {
+    pub fn spawn_empty(&mut self) -> EntityMutSpawner<'_> {
         match self {
-            Spawner::World(w) => EntityMutSpawner::EntityWorldMut(w.spawn_empty()),
-            Spawner::Commands(w) => EntityMutSpawner::EntityCommands(w.spawn_empty()),
+            Spawner::World(w) => EntityMutSpawner::EntityWorldMut(match RESERVED.take() {
+                Some(entity) => w.entity_mut(entity),
+                None => w.spawn_empty(),
+            }),
+            Spawner::Commands(w) => EntityMutSpawner::EntityCommands(match RESERVED.take() {
+                Some(entity) => w.entity(entity),
+                None => w.spawn_empty(),
+            }),
             Spawner::ChildBuilder(w) => EntityMutSpawner::EntityCommands(w.spawn_empty()),
             Spawner::WorldChildBuilder(w) => EntityMutSpawner::EntityWorldMut(w.spawn_empty()),
             Spawner::Scoped(w) => w.spawner_scope(&mut |w| w.spawn_empty().id()),
@@ -124,10 +145,24 @@ impl Spawner<'_, '_, '_> {
     }
 
     /// Spawn a [`Bundle`] with a spawner.
-    pub fn spawn_bundle<B: Bundle>(&mut self, bundle: B) -> EntityMutSpawner {
+    pub fn spawn_bundle<B: Bundle>(&mut self, bundle: B) -> EntityMutSpawner<'_> {
         match self {
-            Spawner::World(w) => EntityMutSpawner::EntityWorldMut(w.spawn(bundle)),
-            Spawner::Commands(w) => EntityMutSpawner::EntityCommands(w.spawn(bundle)),
+            Spawner::World(w) => match RESERVED.take() {
+                Some(entity) => {
+                    let mut entity_mut = w.entity_mut(entity);
+                    entity_mut.insert(bundle);
+                    EntityMutSpawner::EntityWorldMut(entity_mut)
+                }
+                None => EntityMutSpawner::EntityWorldMut(w.spawn(bundle)),
+            },
+            Spawner::Commands(w) => match RESERVED.take() {
+                Some(entity) => {
+                    let mut entity_commands = w.entity(entity);
+                    entity_commands.insert(bundle);
+                    EntityMutSpawner::EntityCommands(entity_commands)
+                }
+                None => EntityMutSpawner::EntityCommands(w.spawn(bundle)),
+            },
             Spawner::ChildBuilder(w) => EntityMutSpawner::EntityCommands(w.spawn(bundle)),
             Spawner::WorldChildBuilder(w) => EntityMutSpawner::EntityWorldMut(w.spawn(bundle)),
             Spawner::Scoped(w) => {
@@ -137,21 +172,169 @@ impl Spawner<'_, '_, '_> {
         }
     }
 
+    /// Obtain a [`EntityMutSpawner`] of an existing [`Entity`].
+    ///
+    /// Only called on spawners pushed by [`Spawner::spawn`], which are never child builders.
+    pub(crate) fn entity_mut(&mut self, entity: Entity) -> EntityMutSpawner<'_> {
+        match self {
+            Spawner::World(w) => EntityMutSpawner::EntityWorldMut(w.entity_mut(entity)),
+            Spawner::Commands(w) => EntityMutSpawner::EntityCommands(w.entity(entity)),
+            Spawner::Scoped(w) => w.spawner_scope(&mut |_| entity),
+            Spawner::ChildBuilder(_) | Spawner::WorldChildBuilder(_) => {
+                unreachable!("Child builders are never pushed by `Spawner::spawn`.")
+            }
+        }
+    }
+
+    /// Spawn an iterator of [`IntoSpawnable`]s with a spawner, returns the spawned entities.
+    ///
+    /// If the spawnables are [`Spawnable::IS_STATIC`], this uses [`World::spawn_batch`],
+    /// or reserves entities and uses [`Commands::insert_or_spawn_batch`] with [`Commands`].
+    ///
+    /// Other spawners spawn each entity individually, which is not faster than calling [`Spawner::spawn`].
+    pub fn spawn_batch<T: IntoSpawnable>(
+        &mut self,
+        spawned: impl IntoIterator<Item = T>,
+    ) -> Vec<Entity> {
+        self.spawn_batch_spawnables(spawned.into_iter().map(|x| x.into_spawnable()))
+    }
+
+    fn spawn_batch_spawnables<S: Spawnable>(
+        &mut self,
+        spawned: impl Iterator<Item = S>,
+    ) -> Vec<Entity> {
+        if !S::IS_STATIC {
+            return spawned.map(|x| self.spawn(x)).collect();
+        }
+        let bundles = spawned.map(|x| x.into_bundle());
+        match self {
+            Spawner::World(w) => w.spawn_batch(bundles).collect(),
+            Spawner::Commands(w) => {
+                let batch: Vec<_> = bundles.map(|x| (w.spawn_empty().id(), x)).collect();
+                let entities = batch.iter().map(|(entity, _)| *entity).collect();
+                w.insert_or_spawn_batch(batch);
+                entities
+            }
+            _ => bundles.map(|x| self.spawn_bundle(x).id()).collect(),
+        }
+    }
+
     /// Spawn a [`IntoSpawnable`] with a spawner.
+    ///
+    /// Children are spawned first, then the entity is spawned with its full bundle,
+    /// and finally children are added to the entity.
+    ///
+    /// With a [`ChildBuilder`] or a [`WorldChildBuilder`], the entity is reserved
+    /// by the builder first, since children spawned by the builder would be added to its parent.
     pub fn spawn(&mut self, spawned: impl IntoSpawnable) -> Entity {
-        let mut spawned = spawned.into_spawnable();
+        self.spawn_spawnable(spawned.into_spawnable())
+    }
+
+    fn spawn_spawnable<S: Spawnable>(&mut self, spawned: S) -> Entity {
+        match self {
+            Spawner::Scoped(w) => {
+                let mut once = Some(spawned);
+                w.spawner_scope(&mut |w| w.spawn_spawnable(once.take().unwrap()))
+                    .id()
+            }
+            Spawner::ChildBuilder(w) if !S::IS_STATIC => {
+                let mut entity = w.spawn_empty();
+                let reserved = entity.id();
+                Spawner::Commands(&mut entity.commands()).spawn_reserved(Some(reserved), spawned)
+            }
+            Spawner::WorldChildBuilder(w) if !S::IS_STATIC => {
+                let entity = w.spawn_empty();
+                let reserved = entity.id();
+                Spawner::World(entity.into_world_mut()).spawn_reserved(Some(reserved), spawned)
+            }
+            _ => self.spawn_reserved(None, spawned),
+        }
+    }
+
+    /// Spawn children, then spawn the entity with its full bundle,
+    /// into `reserved` if specified.
+    fn spawn_reserved<S: Spawnable>(&mut self, reserved: Option<Entity>, mut spawned: S) -> Entity {
+        let mut children = Vec::new();
+        if !S::IS_STATIC {
+            push_spawner(self, Some(&mut children), || {
+                spawned.spawn_children(&mut Spawner::Scoped(Box::new(ThreadLocalSpawner)))
+            });
+        }
+        // for panic safety, this will reset the reserved entity during unwinding.
+        let _reset = ResetReserved(RESERVED.replace(reserved));
         let mut entity_mut = spawned.spawn_mut(self);
-        entity_mut.spawn_children(|mut spawner| spawned.spawn_children(&mut spawner));
-        entity_mut.insert(spawned.into_bundle());
+        if !children.is_empty() {
+            entity_mut.add_children(&children);
+        }
         entity_mut.id()
     }
 }
 
+// Entity reserved by a child builder, used by the next `spawn_empty` or `spawn_bundle`.
+thread_local! {static RESERVED: Cell<Option<Entity>> = const { Cell::new(None) } }
+
+struct ResetReserved(Option<Entity>);
+
+impl Drop for ResetReserved {
+    fn drop(&mut self) {
+        RESERVED.set(self.0);
+    }
+}
+
+/// Type data of a [`Spawnable`] that spawns a reflected value,
+/// registered with `#[reflect(Spawnable)]`.
+#[derive(Clone)]
+pub struct ReflectSpawnable {
+    try_spawn:
+        fn(&dyn Reflect, &mut Spawner, &'static Location<'static>) -> Result<Entity, SpawnError>,
+}
+
+impl ReflectSpawnable {
+    /// Spawn a reflected value with a spawner.
+    ///
+    /// # Panics
+    ///
+    /// If `value` cannot be converted to the registered type via [`FromReflect`].
+    #[track_caller]
+    pub fn spawn(&self, value: &dyn Reflect, spawner: &mut Spawner) -> Entity {
+        self.try_spawn(value, spawner)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Spawn a reflected value with a spawner, returns [`SpawnError::FromReflect`]
+    /// if `value` cannot be converted to the registered type via [`FromReflect`].
+    #[track_caller]
+    pub fn try_spawn(
+        &self,
+        value: &dyn Reflect,
+        spawner: &mut Spawner,
+    ) -> Result<Entity, SpawnError> {
+        (self.try_spawn)(value, spawner, Location::caller())
+    }
+}
+
+impl<T: Spawnable + FromReflect + TypePath> FromType<T> for ReflectSpawnable {
+    fn from_type() -> Self {
+        ReflectSpawnable {
+            try_spawn: |value, spawner, location| {
+                let Some(spawned) = T::from_reflect(value) else {
+                    return Err(SpawnError::FromReflect {
+                        expected: T::type_path(),
+                        found: value.get_represented_type_info().map(|x| x.type_path()),
+                        location,
+                    });
+                };
+                Ok(spawner.spawn_spawnable(spawned))
+            },
+        }
+    }
+}
+
 /// A global dynamic spawner.
 ///
 /// This is meant to support `bevy_defer`.
 pub trait ScopedSpawner {
-    fn spawner_scope(&mut self, f: &mut dyn FnMut(&mut Spawner) -> Entity) -> EntityMutSpawner;
+    fn spawner_scope(&mut self, f: &mut dyn FnMut(&mut Spawner) -> Entity) -> EntityMutSpawner<'_>;
 }
 
 /// A global dynamic spawner.
@@ -161,3 +344,37 @@ pub trait ScopedEntityMut {
     fn id(&self) -> Entity;
     fn entity_mut_scope(&mut self, f: &mut dyn FnMut(&mut EntityMutSpawner));
 }
+
+/// A [`ScopedSpawner`] that spawns using the top of the thread local spawner stack.
+///
+/// This allows [`spawn!`](crate::spawn!) to be used alongside the [`Spawner`]
+/// in [`Spawnable::spawn_children`] without aliasing.
+pub(crate) struct ThreadLocalSpawner;
+
+impl ScopedSpawner for ThreadLocalSpawner {
+    fn spawner_scope(&mut self, f: &mut dyn FnMut(&mut Spawner) -> Entity) -> EntityMutSpawner<'_> {
+        let entity = with_spawner(|spawner, children| {
+            let entity = f(spawner);
+            if let Some(children) = children {
+                children.push(entity);
+            }
+            entity
+        })
+        .expect("Must be called in a spawner scope.");
+        EntityMutSpawner::Scoped(Box::new(ThreadLocalEntityMut(entity)))
+    }
+}
+
+/// A [`ScopedEntityMut`] that accesses an entity using the top of the thread local spawner stack.
+struct ThreadLocalEntityMut(Entity);
+
+impl ScopedEntityMut for ThreadLocalEntityMut {
+    fn id(&self) -> Entity {
+        self.0
+    }
+
+    fn entity_mut_scope(&mut self, f: &mut dyn FnMut(&mut EntityMutSpawner)) {
+        with_spawner(|spawner, _| f(&mut spawner.entity_mut(self.0)))
+            .expect("Must be called in a spawner scope.");
+    }
+}
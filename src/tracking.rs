@@ -0,0 +1,376 @@
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_asset::{
+    Asset, AssetId, AssetServer, Assets, Handle, LoadState, RecursiveDependencyLoadState,
+    UntypedAssetId,
+};
+use bevy_ecs::{
+    bundle::Bundle,
+    component::Component,
+    entity::Entity,
+    event::Event,
+    query::{QueryState, With},
+    system::{Commands, Query, Res},
+    world::World,
+};
+use bevy_hierarchy::BuildWorldChildren;
+use std::marker::PhantomData;
+
+use crate::{
+    asset_server_scope, push_spawner, EntityMutSpawner, Spawnable, Spawner, ThreadLocalSpawner,
+};
+
+/// Returns `Some(failed)` if an asset and its dependencies finished loading.
+pub(crate) fn load_finished(
+    asset_server: &AssetServer,
+    id: impl Into<UntypedAssetId>,
+) -> Option<bool> {
+    let id = id.into();
+    if asset_server.is_loaded_with_dependencies(id) {
+        Some(false)
+    } else if matches!(asset_server.load_state(id), LoadState::Failed(_))
+        || asset_server.recursive_dependency_load_state(id) == RecursiveDependencyLoadState::Failed
+    {
+        Some(true)
+    } else {
+        None
+    }
+}
+
+/// Marker [`Component`] for an entity whose [`Handle<T>`] is loading.
+///
+/// Inserted by [`LoadMe::tracked`](crate::LoadMe::tracked) or manually,
+/// replaced by [`AssetLoaded<T>`] by [`AssetTrackingPlugin<T>`].
+#[derive(Debug, Component)]
+pub struct AssetLoading<T: Asset>(PhantomData<T>);
+
+impl<T: Asset> Default for AssetLoading<T> {
+    fn default() -> Self {
+        AssetLoading(PhantomData)
+    }
+}
+
+/// Marker [`Component`] for an entity whose [`Handle<T>`] finished loading.
+#[derive(Debug, Component)]
+pub struct AssetLoaded<T: Asset> {
+    failed: bool,
+    p: PhantomData<T>,
+}
+
+impl<T: Asset> AssetLoaded<T> {
+    /// Returns true if the asset or one of its dependencies failed to load.
+    pub fn failed(&self) -> bool {
+        self.failed
+    }
+}
+
+/// Observer [`Event`] triggered on an entity when its [`Handle<T>`] finished loading,
+/// alongside [`AssetLoaded<T>`].
+#[derive(Debug, Event)]
+pub struct AssetLoadFinished<T: Asset> {
+    failed: bool,
+    p: PhantomData<T>,
+}
+
+impl<T: Asset> AssetLoadFinished<T> {
+    /// Returns true if the asset or one of its dependencies failed to load.
+    pub fn failed(&self) -> bool {
+        self.failed
+    }
+}
+
+/// A [`Plugin`] that replaces [`AssetLoading<T>`] with [`AssetLoaded<T>`]
+/// and triggers [`AssetLoadFinished<T>`] when a [`Handle<T>`] finished loading.
+///
+/// This also spawns children of [`SpawnWhenLoaded<T>`].
+pub struct AssetTrackingPlugin<T: Asset>(PhantomData<T>);
+
+impl<T: Asset> Default for AssetTrackingPlugin<T> {
+    fn default() -> Self {
+        AssetTrackingPlugin(PhantomData)
+    }
+}
+
+impl<T: Asset> Plugin for AssetTrackingPlugin<T> {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PreUpdate,
+            (track_asset_loading::<T>, spawn_when_loaded::<T>),
+        );
+    }
+}
+
+/// Returns `Some(failed)` if a [`Handle<T>`] finished loading.
+fn handle_finished<T: Asset>(
+    asset_server: &AssetServer,
+    assets: &Assets<T>,
+    handle: &Handle<T>,
+) -> Option<bool> {
+    // Assets added directly to `Assets<T>` are not tracked by the `AssetServer`.
+    let added = || {
+        (asset_server.get_load_state(handle).is_none() && assets.contains(handle)).then_some(false)
+    };
+    load_finished(asset_server, handle).or_else(added)
+}
+
+fn track_asset_loading<T: Asset>(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    assets: Res<Assets<T>>,
+    query: Query<(Entity, &Handle<T>), With<AssetLoading<T>>>,
+) {
+    for (entity, handle) in &query {
+        let Some(failed) = handle_finished(&asset_server, &assets, handle) else {
+            continue;
+        };
+        commands
+            .entity(entity)
+            .remove::<AssetLoading<T>>()
+            .insert(AssetLoaded::<T> {
+                failed,
+                p: PhantomData,
+            });
+        commands.trigger_targets(
+            AssetLoadFinished::<T> {
+                failed,
+                p: PhantomData,
+            },
+            entity,
+        );
+    }
+}
+
+type SpawnFn = Box<dyn FnOnce(&mut Spawner)>;
+
+/// Clones the loaded asset out of [`Assets<T>`], so the world is not borrowed while spawning.
+type OnLoadedFn<T> = Box<dyn FnOnce(&Assets<T>, AssetId<T>) -> Option<SpawnFn> + Send + Sync>;
+
+/// [`Component`] that runs a function when the [`Handle<T>`] on the same entity is loaded,
+/// inserted by [`SpawnWhenLoaded`].
+#[derive(Component)]
+pub struct OnLoaded<T: Asset>(Option<OnLoadedFn<T>>);
+
+/// A [`Spawnable`] that spawns children once the [`Handle<T>`] on the entity is loaded.
+///
+/// The handle can be part of the bundle or inserted later, e.g. by [`LoadMe`](crate::LoadMe).
+/// The function is called with a clone of the loaded asset and a [`Spawner`] that spawns children,
+/// [`spawn!`](crate::spawn!) can also be used to spawn children in the function,
+/// in an [`asset_server_scope`](crate::asset_server_scope),
+/// and is dropped if the asset failed to load. Requires [`AssetTrackingPlugin<T>`].
+///
+/// ```ignore
+/// spawn(SpawnWhenLoaded::new(
+///     LevelBundle { level: asset_server.load("level.ron") },
+///     |level: &Level, spawner: &mut Spawner| {
+///         for enemy in &level.enemies {
+///             spawner.spawn(enemy.clone());
+///         }
+///     }
+/// ))
+/// ```
+pub struct SpawnWhenLoaded<T: Asset, S, F> {
+    spawnable: S,
+    f: F,
+    p: PhantomData<T>,
+}
+
+impl<T, S, F> SpawnWhenLoaded<T, S, F>
+where
+    T: Asset + Clone,
+    S: Spawnable,
+    F: FnOnce(&T, &mut Spawner) + Send + Sync + 'static,
+{
+    /// Create a [`SpawnWhenLoaded`] from a [`Spawnable`] and a function that spawns children.
+    pub fn new(spawnable: S, f: F) -> Self {
+        SpawnWhenLoaded {
+            spawnable,
+            f,
+            p: PhantomData,
+        }
+    }
+
+    fn on_loaded(f: F) -> OnLoaded<T> {
+        OnLoaded(Some(Box::new(move |assets, id| {
+            let asset = assets.get(id)?.clone();
+            Some(Box::new(move |spawner| f(&asset, spawner)))
+        })))
+    }
+}
+
+impl<T, S, F> Spawnable for SpawnWhenLoaded<T, S, F>
+where
+    T: Asset + Clone,
+    S: Spawnable,
+    F: FnOnce(&T, &mut Spawner) + Send + Sync + 'static,
+{
+    const IS_STATIC: bool = S::IS_STATIC;
+
+    fn into_bundle(self) -> impl Bundle {
+        (self.spawnable.into_bundle(), Self::on_loaded(self.f))
+    }
+
+    fn spawn_mut<'t>(self, spawner: &'t mut Spawner) -> EntityMutSpawner<'t> {
+        if S::IS_STATIC {
+            spawner.spawn_bundle(self.into_bundle())
+        } else {
+            let mut entity = self.spawnable.spawn_mut(spawner);
+            entity.insert(Self::on_loaded(self.f));
+            entity
+        }
+    }
+
+    fn spawn_children(&mut self, spawner: &mut Spawner) {
+        self.spawnable.spawn_children(spawner);
+    }
+}
+
+fn spawn_when_loaded<T: Asset>(
+    world: &mut World,
+    query: &mut QueryState<(Entity, &Handle<T>), With<OnLoaded<T>>>,
+) {
+    let asset_server = world.resource::<AssetServer>().clone();
+    let assets = world.resource::<Assets<T>>();
+    let finished: Vec<_> = query
+        .iter(world)
+        .filter(|(_, handle)| handle_finished(&asset_server, assets, handle).is_some())
+        .map(|(entity, handle)| (entity, handle.id()))
+        .collect();
+    for (entity, id) in finished {
+        let Some(f) = world
+            .entity_mut(entity)
+            .take::<OnLoaded<T>>()
+            .and_then(|x| x.0)
+        else {
+            continue;
+        };
+        let Some(f) = f(world.resource::<Assets<T>>(), id) else {
+            continue;
+        };
+        let mut children = Vec::new();
+        push_spawner(&mut Spawner::World(world), Some(&mut children), || {
+            asset_server_scope(&asset_server, || {
+                f(&mut Spawner::Scoped(Box::new(ThreadLocalSpawner)))
+            })
+        });
+        if !children.is_empty() {
+            world.entity_mut(entity).push_children(&children);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy::{
+        ecs::{
+            observer::Trigger,
+            system::{ResMut, Resource},
+        },
+        render::texture::Image,
+    };
+    use bevy_asset::{Assets, Handle};
+    use bevy_ecs::entity::Entity;
+    use bevy_hierarchy::{Children, Parent};
+
+    use crate::{
+        spawn, spawner_scope, test::*, with_world, AssetLoadFinished, AssetLoaded, AssetLoading,
+        AssetTrackingPlugin, LoadMe, OnLoaded, SpawnWhenLoaded, Spawner,
+    };
+
+    #[derive(Default, Resource)]
+    pub struct Finished(Vec<(Entity, bool)>);
+
+    #[test]
+    fn asset_tracking() {
+        let mut app = test_app();
+        app.add_plugins(AssetTrackingPlugin::<Image>::default());
+        app.init_resource::<Finished>();
+        app.observe(
+            |trigger: Trigger<AssetLoadFinished<Image>>, mut finished: ResMut<Finished>| {
+                finished
+                    .0
+                    .push((trigger.entity(), trigger.event().failed()));
+            },
+        );
+        let world = app.world_mut();
+        let handle = world.resource_mut::<Assets<Image>>().add(Image::default());
+        let added = world.spawn((handle, AssetLoading::<Image>::default())).id();
+        // No loader is registered for images.
+        let missing = world
+            .spawn(LoadMe::<Image>::new("circle.png").tracked())
+            .id();
+        world.flush_commands();
+        assert!(world.entity(missing).contains::<AssetLoading<Image>>());
+        update_until(&mut app, |world| world.resource::<Finished>().0.len() == 2);
+        let world = app.world();
+        let mut finished = world.resource::<Finished>().0.clone();
+        finished.sort();
+        let mut expected = vec![(added, false), (missing, true)];
+        expected.sort();
+        assert_eq!(finished, expected);
+        assert!(!world
+            .entity(added)
+            .get::<AssetLoaded<Image>>()
+            .unwrap()
+            .failed());
+        assert!(world
+            .entity(missing)
+            .get::<AssetLoaded<Image>>()
+            .unwrap()
+            .failed());
+        assert!(!world.entity(missing).contains::<AssetLoading<Image>>());
+    }
+
+    #[test]
+    fn spawn_when_loaded() {
+        let mut app = test_app();
+        app.add_plugins(AssetTrackingPlugin::<Image>::default());
+        let world = app.world_mut();
+        let handle = world.resource_mut::<Assets<Image>>().add(Image::default());
+        let [added, missing] = spawner_scope(world, || {
+            [
+                crate::spawn(SpawnWhenLoaded::new(
+                    (A, handle),
+                    |image: &Image, spawner: &mut Spawner| {
+                        assert_eq!(image.width(), 1);
+                        spawner.spawn(B);
+                        spawn!(C => [B]);
+                        // `Assets<Image>` and the asset server are available.
+                        let len = with_world(|w| w.resource::<Assets<Image>>().len());
+                        assert_eq!(len, Some(1));
+                        spawn!(ImageBundle {
+                            image: @asset image.clone()
+                        });
+                        spawn!(ImageBundle {
+                            image: @load "circle.png"
+                        });
+                    },
+                )),
+                crate::spawn(SpawnWhenLoaded::new(
+                    (A, LoadMe::<Image>::new("circle.png").tracked()),
+                    |_: &Image, _: &mut Spawner| panic!("No loader is registered for images."),
+                )),
+            ]
+        });
+        assert!(world.entity(added).get::<Children>().is_none());
+        update_until(&mut app, |world| {
+            world.entity(missing).contains::<AssetLoaded<Image>>()
+        });
+        app.update();
+        let world = app.world();
+        assert_children(world, added, 4);
+        let children = world.entity(added).get::<Children>().unwrap();
+        let handle = world.entity(children[2]).get::<Handle<Image>>().unwrap();
+        assert!(world.resource::<Assets<Image>>().contains(handle));
+        let handle = world.entity(children[3]).get::<Handle<Image>>().unwrap();
+        assert_eq!(handle.path().unwrap().to_string(), "circle.png");
+        for &child in children {
+            assert_eq!(world.entity(child).get::<Parent>().unwrap().get(), added);
+        }
+        let grandchildren = world.entity(children[1]).get::<Children>().unwrap();
+        assert_eq!(grandchildren.len(), 1);
+        let grandchild = world.entity(grandchildren[0]).get::<Parent>().unwrap();
+        assert_eq!(grandchild.get(), children[1]);
+        assert!(!world.entity(added).contains::<OnLoaded<Image>>());
+        assert!(world.entity(missing).get::<Children>().is_none());
+        assert!(!world.entity(missing).contains::<OnLoaded<Image>>());
+    }
+}